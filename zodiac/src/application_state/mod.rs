@@ -1,4 +1,6 @@
 mod repository;
 mod state;
+mod updates;
 pub use repository::*;
-pub use state::*;
\ No newline at end of file
+pub use state::*;
+pub use updates::*;
\ No newline at end of file
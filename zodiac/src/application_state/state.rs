@@ -1,3 +1,5 @@
 use std::fmt::Debug;
 
-pub trait State: Debug + Clone + Copy + Default + PartialEq + 'static {}
\ No newline at end of file
+/// State only needs to be `Clone`: it is snapshotted into the state repository on the first
+/// build and whenever state updates are applied, so it may own heap data such as `String`s.
+pub trait State: Debug + Clone + Default + PartialEq + 'static {}
\ No newline at end of file
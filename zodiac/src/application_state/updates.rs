@@ -0,0 +1,85 @@
+use std::any::{ Any, TypeId };
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{ Hash, Hasher };
+use std::sync::Arc;
+use super::state::State;
+
+pub type StateUpdate = Box<dyn FnOnce(&mut dyn Any) + Send + Sync>;
+
+pub fn create_state_update_queue() -> StateUpdateQueue {
+    StateUpdateQueue::default()
+}
+
+#[derive(Default)]
+pub struct StateUpdateQueue {
+    updates: Vec<StateUpdate>
+}
+
+impl StateUpdateQueue {
+    pub fn push(&mut self, update: StateUpdate) {
+        self.updates.push(update);
+    }
+
+    pub fn take(&mut self) -> Vec<StateUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+}
+
+#[derive(Clone)]
+pub struct TextHandler {
+    handler_type: TypeId,
+    handler: Arc<dyn Fn(&mut dyn Any, &str) + Send + Sync>
+}
+
+impl TextHandler {
+    pub fn new<TState: State, THandler: Fn(&mut TState, &str) + Send + Sync + 'static>(handler: THandler) -> Self {
+        Self {
+            handler_type: TypeId::of::<THandler>(),
+            handler: Arc::new(move |state, text| {
+                if let Some(state) = state.downcast_mut::<TState>() {
+                    handler(state, text);
+                }
+            })
+        }
+    }
+
+    pub fn update_for(&self, text: &str) -> StateUpdate {
+        let handler = self.handler.clone();
+        let text = text.to_string();
+        Box::new(move |state| handler(state, &text))
+    }
+}
+
+impl fmt::Debug for TextHandler {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("TextHandler").field("handler_type", &self.handler_type).finish()
+    }
+}
+
+impl PartialEq for TextHandler {
+    fn eq(&self, other: &Self) -> bool {
+        self.handler_type == other.handler_type
+    }
+}
+
+impl Eq for TextHandler {
+}
+
+impl Hash for TextHandler {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handler_type.hash(state);
+    }
+}
+
+impl PartialOrd for TextHandler {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TextHandler {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.handler_type.cmp(&other.handler_type)
+    }
+}
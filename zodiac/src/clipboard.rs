@@ -0,0 +1,92 @@
+use std::sync::{ Arc, Mutex };
+use log::warn;
+use legion::*;
+
+pub trait Clipboard: Send {
+    fn get_text(&mut self) -> Option<String>;
+    fn set_text(&mut self, text: &str);
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct InMemoryClipboard {
+    text: Option<String>
+}
+
+impl Clipboard for InMemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard
+}
+
+#[cfg(feature = "system-clipboard")]
+impl SystemClipboard {
+    pub fn new() -> Result<Self, arboard::Error> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?
+        })
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.clipboard.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        if let Err(error) = self.clipboard.set_text(text) {
+            warn!("Failed to copy to system clipboard {:?}", error);
+        }
+    }
+}
+
+pub fn create_application_clipboard() -> ApplicationClipboard {
+    ApplicationClipboard::new(InMemoryClipboard::default())
+}
+
+pub fn register_clipboard(resources: &mut Resources) {
+    if !resources.contains::<ApplicationClipboard>() {
+        resources.insert(create_application_clipboard());
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+pub fn register_system_clipboard(resources: &mut Resources) {
+    match SystemClipboard::new() {
+        Ok(clipboard) => resources.insert(ApplicationClipboard::new(clipboard)),
+        Err(error) => {
+            warn!("System clipboard unavailable, falling back to in memory clipboard {:?}", error);
+            register_clipboard(resources);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApplicationClipboard {
+    clipboard: Arc<Mutex<dyn Clipboard>>
+}
+
+impl ApplicationClipboard {
+    pub fn new<TClipboard: Clipboard + 'static>(clipboard: TClipboard) -> Self {
+        Self {
+            clipboard: Arc::new(Mutex::new(clipboard))
+        }
+    }
+
+    pub fn get_text(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().get_text()
+    }
+
+    pub fn set_text(&self, text: &str) {
+        self.clipboard.lock().unwrap().set_text(text);
+    }
+}
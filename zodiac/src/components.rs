@@ -1,10 +1,14 @@
 use std::ops::*;
 use legion::*;
 use serde::*;
+use crate::rendering::rasterise;
+use crate::application_state::TextHandler;
 
 #[derive(Debug)]
 pub enum ZodiacError {
-    FailedToRender(RendererError)
+    FailedToRender(RendererError),
+    FailedToLoadFont(String),
+    FailedToLoadCatalog(String)
 }
 
 impl From<RendererError> for ZodiacError {
@@ -20,6 +24,7 @@ pub enum RendererError {
     FailedToLoadFont,
     BufferSwapError,
     BufferCreationError,
+    TextureCreationError,
     DrawError
 }
 
@@ -75,10 +80,17 @@ pub struct Control {
 pub struct Rebuild {
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Dimensions {
     pub width: u16,
-    pub height: u16
+    pub height: u16,
+    pub scale_factor: f32
+}
+
+impl Default for Dimensions {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
 }
 
 impl Dimensions {
@@ -86,8 +98,22 @@ impl Dimensions {
         Self {
             width,
             height,
+            scale_factor: 1.0
         }
     }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    pub fn logical_width(&self) -> f32 {
+        self.width as f32 / self.scale_factor
+    }
+
+    pub fn logical_height(&self) -> f32 {
+        self.height as f32 / self.scale_factor
+    }
 }
 
 impl From<(u16, u16)> for Dimensions {
@@ -104,28 +130,27 @@ impl From<(u32, u32)> for Dimensions {
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CurrentLayoutConstraints {
-    pub left: u16,
-    pub top: u16,
-    pub width: u16,
-    pub height: u16
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct LayoutRequest {
-    pub left: u16,
-    pub top: u16,
-    pub width: u16,
-    pub height: u16
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32
 }
 
 impl From<&Dimensions> for LayoutRequest {
     fn from(dimensions: &Dimensions) -> Self {
         LayoutRequest {
-            left: 0, 
-            top: 0, 
-            width: 
-            dimensions.width, 
-            height: dimensions.height
+            left: 0.0, 
+            top: 0.0, 
+            width: dimensions.logical_width(), 
+            height: dimensions.logical_height()
         }
     }
 }
@@ -141,6 +166,18 @@ impl From<&CurrentLayoutConstraints> for LayoutRequest {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct LayoutDiagnostics {
+    pub overflow_width: f32,
+    pub overflow_height: f32
+}
+
+impl LayoutDiagnostics {
+    pub fn has_overflowed(&self) -> bool {
+        self.overflow_width > 0.0 || self.overflow_height > 0.0
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct Resized {
 }
@@ -149,11 +186,139 @@ pub struct Resized {
 pub struct Mapped {
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LayoutType {
     Horizontal,
     Vertical,
     Canvas,
+    Grid,
+    Wrap,
+    Dock,
+    Layer,
+    Custom(u16),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Sizing {
+    Fill,
+    WrapContent,
+}
+
+impl Default for Sizing {
+    fn default() -> Self {
+        Sizing::Fill
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Flow {
+    Horizontal,
+    Vertical,
+}
+
+impl Default for Flow {
+    fn default() -> Self {
+        Flow::Horizontal
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LineAlignment {
+    Start,
+    Centre,
+    End,
+}
+
+impl Default for LineAlignment {
+    fn default() -> Self {
+        LineAlignment::Start
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct LineSpacing {
+    pub spacing: f32
+}
+
+impl From<u16> for LineSpacing {
+    fn from(spacing: u16) -> Self {
+        Self {
+            spacing: spacing as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Dock {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Fill,
+}
+
+impl Default for Dock {
+    fn default() -> Self {
+        Dock::Fill
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Alignment {
+    TopLeft,
+    TopCentre,
+    TopRight,
+    CentreLeft,
+    Centre,
+    CentreRight,
+    BottomLeft,
+    BottomCentre,
+    BottomRight,
+    Stretch,
+}
+
+impl Default for Alignment {
+    fn default() -> Self {
+        Alignment::Stretch
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::LeftToRight
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CurrentDirection {
+    pub direction: Direction
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Visibility {
+    Visible,
+    Hidden,
+    Collapsed,
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Visibility::Visible
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Invisible {
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct LayoutOutline {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -173,12 +338,143 @@ impl LayoutContent {
     pub fn vertical() -> Self {
         Self { layout_type: LayoutType::Vertical }
     }
+
+    pub fn grid() -> Self {
+        Self { layout_type: LayoutType::Grid }
+    }
+
+    pub fn wrap() -> Self {
+        Self { layout_type: LayoutType::Wrap }
+    }
+
+    pub fn dock() -> Self {
+        Self { layout_type: LayoutType::Dock }
+    }
+
+    pub fn layer() -> Self {
+        Self { layout_type: LayoutType::Layer }
+    }
+
+    pub fn custom(id: u16) -> Self {
+        Self { layout_type: LayoutType::Custom(id) }
+    }
 }
 
 #[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Renderable {
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GridTrack {
+    Fixed(u16),
+    Fraction(u16),
+    Auto,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rows {
+    pub tracks: Vec<GridTrack>
+}
+
+impl From<Vec<GridTrack>> for Rows {
+    fn from(tracks: Vec<GridTrack>) -> Self {
+        Self {
+            tracks
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Columns {
+    pub tracks: Vec<GridTrack>
+}
+
+impl From<Vec<GridTrack>> for Columns {
+    fn from(tracks: Vec<GridTrack>) -> Self {
+        Self {
+            tracks
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RowGap {
+    pub gap: f32
+}
+
+impl From<u16> for RowGap {
+    fn from(gap: u16) -> Self {
+        Self {
+            gap: gap as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColumnGap {
+    pub gap: f32
+}
+
+impl From<u16> for ColumnGap {
+    fn from(gap: u16) -> Self {
+        Self {
+            gap: gap as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Row {
+    pub row: u16
+}
+
+impl From<u16> for Row {
+    fn from(row: u16) -> Self {
+        Self {
+            row
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Column {
+    pub column: u16
+}
+
+impl From<u16> for Column {
+    fn from(column: u16) -> Self {
+        Self {
+            column
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RowSpan {
+    pub span: u16
+}
+
+impl From<u16> for RowSpan {
+    fn from(span: u16) -> Self {
+        Self {
+            span
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSpan {
+    pub span: u16
+}
+
+impl From<u16> for ColumnSpan {
+    fn from(span: u16) -> Self {
+        Self {
+            span
+        }
+    }
+}
+
 
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
@@ -215,28 +511,336 @@ impl From<u8> for FontSize {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct FontFamily {
+    pub family: String
+}
+
+impl From<&str> for FontFamily {
+    fn from(family: &str) -> Self {
+        Self {
+            family: family.to_string()
+        }
+    }
+}
+
+impl From<String> for FontFamily {
+    fn from(family: String) -> Self {
+        Self {
+            family
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FontWeight {
+    pub weight: u16
+}
+
+impl FontWeight {
+    pub const NORMAL: FontWeight = FontWeight { weight: 400 };
+    pub const BOLD: FontWeight = FontWeight { weight: 700 };
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+impl From<u16> for FontWeight {
+    fn from(weight: u16) -> Self {
+        Self {
+            weight
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    Oblique
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        FontStyle::Normal
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct FontId {
+    pub id: usize
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TextWrap {
+    None,
+    Word,
+    Character
+}
+
+impl Default for TextWrap {
+    fn default() -> Self {
+        TextWrap::None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TextAlign {
+    Start,
+    Center,
+    End,
+    Justify
+}
+
+impl Default for TextAlign {
+    fn default() -> Self {
+        TextAlign::Start
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct LineHeight {
+    pub height: f32
+}
+
+impl From<u16> for LineHeight {
+    fn from(height: u16) -> Self {
+        Self {
+            height: height as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct MaxLines {
+    pub lines: u16
+}
+
+impl From<u16> for MaxLines {
+    fn from(lines: u16) -> Self {
+        Self {
+            lines
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Underline {
+    pub underline: bool
+}
+
+impl From<bool> for Underline {
+    fn from(underline: bool) -> Self {
+        Self {
+            underline
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Value {
+    pub text: String
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Self {
+            text
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Placeholder {
+    pub text: String
+}
+
+impl From<String> for Placeholder {
+    fn from(text: String) -> Self {
+        Self {
+            text
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct MultiLine {
+    pub multi_line: bool
+}
+
+impl From<bool> for MultiLine {
+    fn from(multi_line: bool) -> Self {
+        Self {
+            multi_line
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Editable {
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Selectable {
+    pub selectable: bool
+}
+
+impl From<bool> for Selectable {
+    fn from(selectable: bool) -> Self {
+        Self {
+            selectable
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnChange {
+    pub handler: TextHandler
+}
+
+impl From<TextHandler> for OnChange {
+    fn from(handler: TextHandler) -> Self {
+        Self {
+            handler
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnSubmit {
+    pub handler: TextHandler
+}
+
+impl From<TextHandler> for OnSubmit {
+    fn from(handler: TextHandler) -> Self {
+        Self {
+            handler
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Background {
+    pub colour: Colour
+}
+
+impl From<(u8, u8, u8, u8)> for Background {
+    fn from(colour: (u8, u8, u8, u8)) -> Self {
+        Self {
+            colour: Colour::from(colour)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextGlyph {
+    pub id: u16,
+    pub cluster: usize,
+    pub left: f32,
+    pub top: f32,
+    pub advance: f32
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextRun {
+    pub text: String,
+    pub glyphs: Vec<TextGlyph>,
+    pub direction: Direction,
+    pub font: FontId,
+    pub font_size: f32,
+    pub colour: Option<Colour>,
+    pub underline: bool,
+    pub background: Option<Colour>,
+    pub offset: usize,
+    pub left: f32,
+    pub top: f32,
+    pub width: f32
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextLines {
+    pub runs: Vec<TextRun>,
+    pub line_height: f32,
+    pub baseline: f32
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct Left {
-    pub left: u16
+    pub left: f32
 }
 
 impl From<u16> for Left {
     fn from(left: u16) -> Self {
         Self {
-            left
+            left: left as f32
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct Top {
-    pub top: u16
+    pub top: f32
 }
 
 impl From<u16> for Top {
     fn from(top: u16) -> Self {
         Self {
-            top
+            top: top as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Right {
+    pub right: f32
+}
+
+impl From<u16> for Right {
+    fn from(right: u16) -> Self {
+        Self {
+            right: right as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Bottom {
+    pub bottom: f32
+}
+
+impl From<u16> for Bottom {
+    fn from(bottom: u16) -> Self {
+        Self {
+            bottom: bottom as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CenterX {
+    pub offset: f32
+}
+
+impl From<i16> for CenterX {
+    fn from(offset: i16) -> Self {
+        Self {
+            offset: offset as f32
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CenterY {
+    pub offset: f32
+}
+
+impl From<i16> for CenterY {
+    fn from(offset: i16) -> Self {
+        Self {
+            offset: offset as f32
         }
     }
 }
@@ -247,10 +851,23 @@ pub struct OffsetsMapped {
 
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct LayoutChange {
-    pub left: u16,
-    pub top: u16,
-    pub width: u16,
-    pub height: u16
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32
+}
+
+impl LayoutChange {
+    pub fn raster_position(&self, scale_factor: f32) -> [u16; 2] {
+        [rasterise(self.left, scale_factor), rasterise(self.top, scale_factor)]
+    }
+
+    pub fn raster_dimensions(&self, scale_factor: f32) -> [u16; 2] {
+        [
+            rasterise(self.left + self.width, scale_factor).saturating_sub(rasterise(self.left, scale_factor)),
+            rasterise(self.top + self.height, scale_factor).saturating_sub(rasterise(self.top, scale_factor))
+        ]
+    }
 }
 
 impl Add<Left> for LayoutChange {
@@ -271,13 +888,13 @@ impl Add<Top> for LayoutChange {
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Width {
-    pub width: u16
+    pub width: f32
 }
 
 impl From<u16> for Width {
     fn from(width: u16) -> Self {
         Self {
-            width
+            width: width as f32
         }
     }
 }
@@ -285,26 +902,26 @@ impl From<u16> for Width {
 impl From<&Radius> for Width {
     fn from(radius: &Radius) -> Self {
         Width {
-            width: radius.radius
+            width: radius.radius as f32
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MinimumWidth {
-    pub width: u16
+    pub width: f32
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Height {
-    pub height: u16
+    pub height: f32
 }
 
 
 impl From<u16> for Height {
     fn from(height: u16) -> Self {
         Self {
-            height
+            height: height as f32
         }
     }
 }
@@ -312,14 +929,42 @@ impl From<u16> for Height {
 impl From<&Radius> for Height {
     fn from(radius: &Radius) -> Self {
         Height {
-            height: radius.radius
+            height: radius.radius as f32
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MinimumHeight {
-    pub height: u16
+    pub height: f32
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AspectRatio {
+    pub ratio: f32
+}
+
+impl AspectRatio {
+    pub fn width_for(&self, height: f32) -> f32 {
+        height * self.ratio
+    }
+
+    pub fn height_for(&self, width: f32) -> f32 {
+        width / self.ratio
+    }
+
+    pub fn fit(&self, width: f32, height: f32) -> (f32, f32) {
+        let width = width.min(self.width_for(height));
+        (width, self.height_for(width))
+    }
+}
+
+impl From<(u16, u16)> for AspectRatio {
+    fn from(ratio: (u16, u16)) -> Self {
+        Self {
+            ratio: ratio.0.max(1) as f32 / ratio.1.max(1) as f32
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
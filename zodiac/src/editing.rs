@@ -0,0 +1,670 @@
+use serde::*;
+use crate::components::*;
+use crate::events::*;
+use crate::rendering::rasterise;
+
+const MAX_UNDO_STEPS: usize = 100;
+const CARET_WIDTH: f32 = 1.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum EditKind {
+    Typing,
+    Deleting
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+struct TextEditSnapshot {
+    value: String,
+    caret: usize,
+    anchor: usize
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct TextEditOutcome {
+    pub changed: bool,
+    pub submitted: bool
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TextSelection {
+    pub anchor: usize,
+    pub caret: usize,
+    pub dragging: bool
+}
+
+impl TextSelection {
+    pub fn selection(&self) -> (usize, usize) {
+        (self.caret.min(self.anchor), self.caret.max(self.anchor))
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    pub fn select(&mut self, to: usize, extend: bool) -> bool {
+        let previous = *self;
+        self.caret = to;
+        if !extend {
+            self.anchor = to;
+        }
+        previous.selection() != self.selection()
+    }
+
+    pub fn clear(&mut self) -> bool {
+        let had_selection = self.has_selection();
+        self.anchor = self.caret;
+        self.dragging = false;
+        had_selection
+    }
+
+    pub fn selected_text<'a>(&self, text: &'a str) -> &'a str {
+        let (start, end) = self.selection();
+        let clamp = |position: usize| {
+            let mut position = position.min(text.len());
+            while !text.is_char_boundary(position) {
+                position -= 1;
+            }
+            position
+        };
+        &text[clamp(start)..clamp(end)]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextComposition {
+    pub text: String,
+    pub cursor: usize
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextEditState {
+    pub value: String,
+    pub caret: usize,
+    pub anchor: usize,
+    pub focused: bool,
+    pub scroll_left: f32,
+    pub scroll_top: f32,
+    pub composition: Option<TextComposition>,
+    bound: Option<String>,
+    emitted: Vec<String>,
+    undo: Vec<TextEditSnapshot>,
+    redo: Vec<TextEditSnapshot>,
+    last_edit: Option<EditKind>
+}
+
+impl TextEditState {
+    pub fn bind(&mut self, value: &str) -> bool {
+        if self.bound.as_deref() == Some(value) {
+            return false;
+        }
+        self.bound = Some(value.to_string());
+
+        if let Some(index) = self.emitted.iter().position(|emitted| emitted == value) {
+            self.emitted.drain(..=index);
+            return false;
+        }
+        if self.value == value {
+            return false;
+        }
+
+        self.value = value.to_string();
+        self.caret = self.value.len();
+        self.anchor = self.caret;
+        self.composition = None;
+        self.emitted.clear();
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+        true
+    }
+
+    pub fn emit(&mut self) -> &str {
+        self.emitted.push(self.value.clone());
+        if self.emitted.len() > MAX_UNDO_STEPS {
+            self.emitted.remove(0);
+        }
+        &self.value
+    }
+
+    pub fn display_text(&self, placeholder: Option<&Placeholder>) -> String {
+        match (&self.composition, placeholder) {
+            (Some(composition), _) => {
+                let mut text = self.value.clone();
+                text.insert_str(self.clamp(self.caret), &composition.text);
+                text
+            },
+            (None, Some(placeholder)) if self.value.is_empty() => placeholder.text.clone(),
+            _ => self.value.clone()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty() && self.composition.is_none()
+    }
+
+    pub fn is_composing(&self) -> bool {
+        self.composition.is_some()
+    }
+
+    pub fn composition_range(&self) -> Option<(usize, usize)> {
+        let start = self.clamp(self.caret);
+        self.composition
+            .as_ref()
+            .map(|composition| (start, start + composition.text.len()))
+    }
+
+    pub fn display_caret(&self) -> usize {
+        self.clamp(self.caret) + self.composition
+            .as_ref()
+            .map_or(0, |composition| composition.cursor)
+    }
+
+    pub fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    pub fn blur(&mut self) {
+        self.focused = false;
+        self.composition = None;
+        self.last_edit = None;
+    }
+
+    pub fn selection(&self) -> (usize, usize) {
+        (self.caret.min(self.anchor), self.caret.max(self.anchor))
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    pub fn selected_text(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.value[start..end]
+    }
+
+    pub fn move_caret(&mut self, to: usize, extend: bool) {
+        self.caret = self.clamp(to);
+        if !extend {
+            self.anchor = self.caret;
+        }
+        self.last_edit = None;
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.value.len();
+        self.last_edit = None;
+    }
+
+    pub fn move_left(&mut self, extend: bool, word: bool) {
+        if self.has_selection() && !extend {
+            self.move_caret(self.selection().0, false);
+        } else if word {
+            self.move_caret(self.previous_word(self.caret), extend);
+        } else {
+            self.move_caret(self.previous_char(self.caret), extend);
+        }
+    }
+
+    pub fn move_right(&mut self, extend: bool, word: bool) {
+        if self.has_selection() && !extend {
+            self.move_caret(self.selection().1, false);
+        } else if word {
+            self.move_caret(self.next_word(self.caret), extend);
+        } else {
+            self.move_caret(self.next_char(self.caret), extend);
+        }
+    }
+
+    pub fn insert(&mut self, text: &str) -> bool {
+        if text.is_empty() && !self.has_selection() {
+            return false;
+        }
+
+        let coalesces = !self.has_selection() && !text.chars().any(char::is_whitespace);
+        self.record(if coalesces { Some(EditKind::Typing) } else { None });
+        let (start, end) = self.selection();
+        self.value.replace_range(start..end, text);
+        self.caret = start + text.len();
+        self.anchor = self.caret;
+        true
+    }
+
+    pub fn backspace(&mut self, word: bool) -> bool {
+        if self.has_selection() {
+            return self.delete_selection();
+        }
+        if self.caret == 0 {
+            return false;
+        }
+
+        let start = if word { self.previous_word(self.caret) } else { self.previous_char(self.caret) };
+        self.record(Some(EditKind::Deleting));
+        self.value.replace_range(start..self.caret, "");
+        self.caret = start;
+        self.anchor = start;
+        true
+    }
+
+    pub fn delete(&mut self, word: bool) -> bool {
+        if self.has_selection() {
+            return self.delete_selection();
+        }
+        if self.caret == self.value.len() {
+            return false;
+        }
+
+        let end = if word { self.next_word(self.caret) } else { self.next_char(self.caret) };
+        self.record(Some(EditKind::Deleting));
+        self.value.replace_range(self.caret..end, "");
+        self.anchor = self.caret;
+        true
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.redo.push(current);
+                self.restore(snapshot);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.undo.push(current);
+                self.restore(snapshot);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn apply_key(&mut self, key: KeyCode, modifiers: KeyModifiers, lines: Option<&TextLines>, multi_line: bool) -> TextEditOutcome {
+        let mut outcome = TextEditOutcome::default();
+        let extend = modifiers.shift;
+        let word = modifiers.control;
+
+        match key {
+            KeyCode::Letter(letter) if modifiers.control => match letter.to_ascii_lowercase() {
+                'a' => self.select_all(),
+                'z' if modifiers.shift => outcome.changed = self.redo(),
+                'z' => outcome.changed = self.undo(),
+                'y' => outcome.changed = self.redo(),
+                _ => {}
+            },
+            KeyCode::Left => self.move_left(extend, word),
+            KeyCode::Right => self.move_right(extend, word),
+            KeyCode::Up => self.move_vertically(lines, false, extend),
+            KeyCode::Down => self.move_vertically(lines, true, extend),
+            KeyCode::Home if modifiers.control => self.move_caret(0, extend),
+            KeyCode::End if modifiers.control => self.move_caret(self.value.len(), extend),
+            KeyCode::Home => self.move_caret(self.line_bounds(lines).0, extend),
+            KeyCode::End => self.move_caret(self.line_bounds(lines).1, extend),
+            KeyCode::Backspace => outcome.changed = self.backspace(word),
+            KeyCode::Delete => outcome.changed = self.delete(word),
+            KeyCode::Enter if multi_line && !modifiers.control => outcome.changed = self.insert("\n"),
+            KeyCode::Enter => outcome.submitted = true,
+            KeyCode::Escape => self.move_caret(self.caret, false),
+            KeyCode::Tab | KeyCode::Letter(_) => {}
+        }
+
+        outcome
+    }
+
+    pub fn apply_text(&mut self, text: &str) -> bool {
+        let text: String = text
+            .chars()
+            .filter(|character| !character.is_control())
+            .collect();
+        if text.is_empty() {
+            return false;
+        }
+        self.insert(&text)
+    }
+
+    pub fn update_composition(&mut self, text: &str, cursor: usize) -> bool {
+        let changed = self.has_selection() && self.delete_selection();
+        if text.is_empty() {
+            self.composition = None;
+            return changed;
+        }
+
+        let mut cursor = cursor.min(text.len());
+        while !text.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        self.composition = Some(TextComposition {
+            text: text.to_string(),
+            cursor
+        });
+        changed
+    }
+
+    pub fn commit_composition(&mut self, text: &str) -> bool {
+        self.composition = None;
+        self.last_edit = None;
+        self.apply_text(text)
+    }
+
+    pub fn cancel_composition(&mut self) -> bool {
+        self.composition.take().is_some()
+    }
+
+    pub fn scroll_to_caret(&mut self, lines: &TextLines, width: f32, height: f32) {
+        let content_width = lines.runs.iter().fold(0.0, |content_width: f32, run| content_width.max(run.left + run.width));
+        let content_height = lines.runs.iter().fold(0.0, |content_height: f32, run| content_height.max(run.top + lines.line_height));
+        self.scroll_left = self.scroll_left.min(content_width + CARET_WIDTH - width).max(0.0);
+        self.scroll_top = self.scroll_top.min(content_height - height).max(0.0);
+
+        if !self.focused {
+            return;
+        }
+
+        if let Some((left, top)) = caret_offset(lines, self.display_caret()) {
+            if left < self.scroll_left {
+                self.scroll_left = left;
+            } else if left + CARET_WIDTH > self.scroll_left + width {
+                self.scroll_left = left + CARET_WIDTH - width;
+            }
+            if top < self.scroll_top {
+                self.scroll_top = top;
+            } else if top + lines.line_height > self.scroll_top + height {
+                self.scroll_top = top + lines.line_height - height;
+            }
+        }
+    }
+
+    fn move_vertically(&mut self, lines: Option<&TextLines>, down: bool, extend: bool) {
+        if let Some((lines, (left, top))) = lines.and_then(|lines| caret_offset(lines, self.caret).map(|offset| (lines, offset))) {
+            let target = if down { top + lines.line_height } else { top - lines.line_height };
+            let has_line = lines.runs.iter().any(|run| (run.top - target).abs() < lines.line_height / 2.0);
+            if has_line {
+                self.move_caret(caret_at(lines, left, target + lines.line_height / 2.0), extend);
+                return;
+            }
+        } else if let Some(target) = self.paragraph_vertically(down) {
+            self.move_caret(target, extend);
+            return;
+        }
+        self.move_caret(if down { self.value.len() } else { 0 }, extend);
+    }
+
+    fn paragraph_vertically(&self, down: bool) -> Option<usize> {
+        let (start, end) = self.paragraph_bounds(self.caret);
+        let column = self.value[start..self.caret].chars().count();
+        let target_start = if down {
+            if end == self.value.len() {
+                return None;
+            }
+            end + 1
+        } else {
+            if start == 0 {
+                return None;
+            }
+            self.paragraph_bounds(start - 1).0
+        };
+        let target_end = self.paragraph_bounds(target_start).1;
+        Some(self.value[target_start..target_end]
+            .char_indices()
+            .nth(column)
+            .map_or(target_end, |(offset, _)| target_start + offset))
+    }
+
+    fn line_bounds(&self, lines: Option<&TextLines>) -> (usize, usize) {
+        lines
+            .and_then(|lines| line_bounds(lines, self.caret))
+            .map(|(start, end)| (self.clamp(start), self.clamp(end)))
+            .unwrap_or_else(|| self.paragraph_bounds(self.caret))
+    }
+
+    fn paragraph_bounds(&self, position: usize) -> (usize, usize) {
+        (
+            self.value[..position].rfind('\n').map_or(0, |index| index + 1),
+            self.value[position..].find('\n').map_or(self.value.len(), |index| position + index)
+        )
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let (start, end) = self.selection();
+        self.record(None);
+        self.value.replace_range(start..end, "");
+        self.caret = start;
+        self.anchor = start;
+        true
+    }
+
+    fn record(&mut self, kind: Option<EditKind>) {
+        if kind.is_none() || kind != self.last_edit {
+            let snapshot = self.snapshot();
+            self.undo.push(snapshot);
+            if self.undo.len() > MAX_UNDO_STEPS {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last_edit = kind;
+    }
+
+    fn snapshot(&self) -> TextEditSnapshot {
+        TextEditSnapshot {
+            value: self.value.clone(),
+            caret: self.caret,
+            anchor: self.anchor
+        }
+    }
+
+    fn restore(&mut self, snapshot: TextEditSnapshot) {
+        self.value = snapshot.value;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+    }
+
+    fn clamp(&self, position: usize) -> usize {
+        let mut position = position.min(self.value.len());
+        while !self.value.is_char_boundary(position) {
+            position -= 1;
+        }
+        position
+    }
+
+    fn previous_char(&self, position: usize) -> usize {
+        self.value[..position].char_indices().next_back().map_or(0, |(offset, _)| offset)
+    }
+
+    fn next_char(&self, position: usize) -> usize {
+        self.value[position..].chars().next().map_or(position, |character| position + character.len_utf8())
+    }
+
+    fn previous_word(&self, position: usize) -> usize {
+        let mut chars = self.value[..position].char_indices().rev().skip_while(|(_, character)| character.is_whitespace()).peekable();
+        let class = match chars.peek() {
+            Some((_, character)) => char_class(*character),
+            None => return 0
+        };
+        chars
+            .take_while(|(_, character)| char_class(*character) == class)
+            .last()
+            .map_or(position, |(offset, _)| offset)
+    }
+
+    fn next_word(&self, position: usize) -> usize {
+        let rest = &self.value[position..];
+        let class = match rest.chars().next() {
+            Some(character) => char_class(character),
+            None => return position
+        };
+        rest.char_indices()
+            .skip_while(|(_, character)| class != CharClass::Space && char_class(*character) == class)
+            .find(|(_, character)| !character.is_whitespace())
+            .map_or(self.value.len(), |(offset, _)| position + offset)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punctuation
+}
+
+fn char_class(character: char) -> CharClass {
+    if character.is_whitespace() {
+        CharClass::Space
+    } else if character.is_alphanumeric() || character == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+fn run_end(run: &TextRun) -> usize {
+    run.offset + run.text.len()
+}
+
+fn caret_run(lines: &TextLines, caret: usize) -> Option<&TextRun> {
+    lines.runs
+        .iter()
+        .find(|run| run.offset <= caret && caret < run_end(run))
+        .or_else(|| lines.runs.iter().find(|run| run_end(run) == caret))
+}
+
+fn run_caret_left(run: &TextRun, local: usize) -> f32 {
+    let advance: f32 = run.glyphs
+        .iter()
+        .filter(|glyph| glyph.cluster < local)
+        .map(|glyph| glyph.advance)
+        .sum();
+
+    match run.direction {
+        Direction::LeftToRight => run.left + advance,
+        Direction::RightToLeft => run.left + run.width - advance
+    }
+}
+
+pub fn caret_offset(lines: &TextLines, caret: usize) -> Option<(f32, f32)> {
+    caret_run(lines, caret).map(|run| (run_caret_left(run, caret - run.offset), run.top))
+}
+
+pub fn caret_at(lines: &TextLines, left: f32, top: f32) -> usize {
+    let line_distance = |run: &TextRun| {
+        if top < run.top {
+            run.top - top
+        } else {
+            (top - run.top - lines.line_height).max(0.0)
+        }
+    };
+    let line_top = match lines.runs.iter().min_by(|first, second| line_distance(first).total_cmp(&line_distance(second))) {
+        Some(run) => run.top,
+        None => return 0
+    };
+
+    lines.runs
+        .iter()
+        .filter(|run| run.top == line_top)
+        .flat_map(|run| run.text
+            .char_indices()
+            .map(|(local, _)| local)
+            .chain(Some(run.text.len()))
+            .map(move |local| (run.offset + local, (run_caret_left(run, local) - left).abs())))
+        .min_by(|(_, first), (_, second)| first.total_cmp(second))
+        .map_or(0, |(caret, _)| caret)
+}
+
+pub fn line_bounds(lines: &TextLines, caret: usize) -> Option<(usize, usize)> {
+    let top = caret_run(lines, caret)?.top;
+    let line = lines.runs.iter().filter(|run| run.top == top);
+    Some((
+        line.clone().map(|run| run.offset).min()?,
+        line.map(run_end).max()?
+    ))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct TextRect {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32
+}
+
+impl TextRect {
+    pub fn raster_position(&self, scale_factor: f32) -> [u16; 2] {
+        [rasterise(self.left, scale_factor), rasterise(self.top, scale_factor)]
+    }
+
+    pub fn raster_dimensions(&self, scale_factor: f32) -> [u16; 2] {
+        [
+            rasterise(self.left + self.width, scale_factor).saturating_sub(rasterise(self.left, scale_factor)),
+            rasterise(self.top + self.height, scale_factor).saturating_sub(rasterise(self.top, scale_factor))
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextEditGeometry {
+    pub caret: Option<TextRect>,
+    pub selections: Vec<TextRect>
+}
+
+pub fn text_edit_geometry(lines: &TextLines, state: &TextEditState, left: f32, top: f32) -> TextEditGeometry {
+    let left = left - state.scroll_left;
+    let top = top - state.scroll_top;
+    let (start, end) = state.selection();
+    let selections = selection_rects(lines, start, end, left, top);
+
+    let caret = caret_offset(lines, state.display_caret())
+        .filter(|_| state.focused)
+        .map(|(caret_left, caret_top)| TextRect {
+            left: left + caret_left,
+            top: top + caret_top,
+            width: CARET_WIDTH,
+            height: lines.line_height
+        });
+
+    TextEditGeometry {
+        caret,
+        selections
+    }
+}
+
+pub fn selection_rects(lines: &TextLines, start: usize, end: usize, left: f32, top: f32) -> Vec<TextRect> {
+    lines.runs
+        .iter()
+        .filter_map(|run| {
+            let selected_start = start.max(run.offset);
+            let selected_end = end.min(run_end(run));
+            if selected_start >= selected_end {
+                return None;
+            }
+            let first = run_caret_left(run, selected_start - run.offset);
+            let second = run_caret_left(run, selected_end - run.offset);
+            Some(TextRect {
+                left: left + first.min(second),
+                top: top + run.top,
+                width: (second - first).abs(),
+                height: lines.line_height
+            })
+        })
+        .collect()
+}
+
+pub fn selection_colour(colour: Colour) -> Colour {
+    Colour {
+        a: colour.a / 4,
+        ..colour
+    }
+}
+
+pub fn placeholder_colour(colour: Colour) -> Colour {
+    Colour {
+        a: colour.a / 2,
+        ..colour
+    }
+}
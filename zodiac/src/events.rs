@@ -6,9 +6,67 @@ pub enum SystemWindowEventType {
     CloseRequested
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyCode {
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Backspace,
+    Delete,
+    Enter,
+    Escape,
+    Tab,
+    Letter(char)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub logo: bool
+}
+
+impl KeyModifiers {
+    pub fn shift() -> Self {
+        Self {
+            shift: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn control() -> Self {
+        Self {
+            control: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SystemInputEventType {
+    KeyPressed(KeyCode, KeyModifiers),
+    TextReceived(String),
+    CompositionUpdated(String, usize),
+    CompositionCommitted(String),
+    CompositionCancelled,
+    PointerPressed(f32, f32, KeyModifiers),
+    PointerMoved(f32, f32, KeyModifiers),
+    PointerReleased(f32, f32, KeyModifiers)
+}
+
 pub enum SystemEvent {
     Unused,
     Window(SystemWindowEventType),
+    Input(SystemInputEventType),
     Device()
 }
 
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::path::Path;
+use ab_glyph::{ point, Font, FontArc, FontRef, FontVec, GlyphId, PxScale };
+use std::sync::Arc;
+use legion::*;
+use self_cell::self_cell;
+use crate::*;
+
+static DEFAULT_FONT: &[u8] = include_bytes!("../fonts/DejaVuSans.ttf");
+pub const DEFAULT_FONT_FAMILY: &str = "DejaVu Sans";
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct FontSelection {
+    pub family: Option<String>,
+    pub weight: FontWeight,
+    pub style: FontStyle
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GlyphCoverage {
+    pub left: f32,
+    pub top: f32,
+    pub width: usize,
+    pub height: usize,
+    pub coverage: Vec<f32>
+}
+
+type Shaper<'a> = Option<rustybuzz::Face<'a>>;
+
+self_cell!(
+    struct ShapedFont {
+        owner: FontArc,
+
+        #[covariant]
+        dependent: Shaper,
+    }
+);
+
+#[derive(Clone)]
+pub struct FontFace {
+    pub family: String,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    shaped: Arc<ShapedFont>
+}
+
+impl FontFace {
+    fn new(family: &str, weight: FontWeight, style: FontStyle, font: FontArc) -> Self {
+        Self {
+            family: family.to_string(),
+            weight,
+            style,
+            shaped: Arc::new(ShapedFont::new(font, |font| rustybuzz::Face::from_slice(font.font_data(), 0)))
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.font().font_data()
+    }
+
+    pub fn shaper(&self) -> Option<&rustybuzz::Face<'_>> {
+        self.shaped.borrow_dependent().as_ref()
+    }
+
+    fn font(&self) -> &FontArc {
+        self.shaped.borrow_owner()
+    }
+
+    pub fn has_glyph(&self, character: char) -> bool {
+        self.glyph_id(character) != 0
+    }
+
+    pub fn glyph_id(&self, character: char) -> u16 {
+        self.font().glyph_id(character).0
+    }
+
+    pub fn advance(&self, character: char, font_size: f32) -> f32 {
+        self.font().h_advance_unscaled(self.font().glyph_id(character)) * self.scale(font_size)
+    }
+
+    pub fn kern(&self, previous: char, character: char, font_size: f32) -> f32 {
+        self.font().kern_unscaled(self.font().glyph_id(previous), self.font().glyph_id(character)) * self.scale(font_size)
+    }
+
+    pub fn ascent(&self, font_size: f32) -> f32 {
+        self.font().ascent_unscaled() * self.scale(font_size)
+    }
+
+    pub fn rasterise_glyph(&self, glyph_id: u16, font_size: f32) -> Option<GlyphCoverage> {
+        let scale = PxScale::from(self.font().height_unscaled() * self.scale(font_size));
+        let outlined = self.font().outline_glyph(GlyphId(glyph_id).with_scale_and_position(scale, point(0.0, 0.0)))?;
+        let bounds = outlined.px_bounds();
+        let width = bounds.width() as usize;
+        let height = bounds.height() as usize;
+        let mut coverage = vec!(0.0; width * height);
+        outlined.draw(|x, y, value| {
+            if let Some(pixel) = coverage.get_mut(y as usize * width + x as usize) {
+                *pixel = value;
+            }
+        });
+        Some(GlyphCoverage {
+            left: bounds.min.x,
+            top: bounds.min.y,
+            width,
+            height,
+            coverage
+        })
+    }
+
+    pub fn line_height(&self, font_size: f32) -> f32 {
+        let font = self.font();
+        (font.ascent_unscaled() - font.descent_unscaled() + font.line_gap_unscaled()) * self.scale(font_size)
+    }
+
+    pub fn scale(&self, font_size: f32) -> f32 {
+        font_size / self.font().units_per_em().unwrap_or(1.0)
+    }
+}
+
+#[derive(Default)]
+pub struct FontRegistry {
+    faces: Vec<FontFace>,
+    families: HashMap<String, Vec<FontId>>,
+    fallbacks: HashMap<String, Vec<String>>,
+    default_fallbacks: Vec<String>
+}
+
+pub fn create_font_registry() -> Result<FontRegistry, ZodiacError> {
+    let mut registry = FontRegistry::default();
+    registry.register_embedded(DEFAULT_FONT_FAMILY, FontWeight::NORMAL, FontStyle::Normal, DEFAULT_FONT)?;
+    registry.add_default_fallback(DEFAULT_FONT_FAMILY);
+    Ok(registry)
+}
+
+pub fn register_fonts(
+    resources: &mut Resources,
+    register: impl FnOnce(&mut FontRegistry) -> Result<(), ZodiacError>) -> Result<(), ZodiacError> {
+    if !resources.contains::<FontRegistry>() {
+        resources.insert(create_font_registry()?);
+    }
+    let mut registry = resources.get_mut::<FontRegistry>().unwrap();
+    register(&mut *registry)
+}
+
+impl FontRegistry {
+    pub fn register_embedded(&mut self, family: &str, weight: FontWeight, style: FontStyle, data: &'static [u8]) -> Result<FontId, ZodiacError> {
+        match FontRef::try_from_slice(data) {
+            Ok(font) => Ok(self.register_face(FontFace::new(family, weight, style, FontArc::from(font)))),
+            Err(_) => Err(ZodiacError::FailedToLoadFont(family.to_string()))
+        }
+    }
+
+    pub fn register_file<P: AsRef<Path>>(&mut self, family: &str, weight: FontWeight, style: FontStyle, path: P) -> Result<FontId, ZodiacError> {
+        match std::fs::read(path.as_ref()) {
+            Ok(data) => self.register_data(family, weight, style, data),
+            Err(_) => Err(ZodiacError::FailedToLoadFont(path.as_ref().display().to_string()))
+        }
+    }
+
+    pub fn register_data(&mut self, family: &str, weight: FontWeight, style: FontStyle, data: Vec<u8>) -> Result<FontId, ZodiacError> {
+        match FontVec::try_from_vec(data) {
+            Ok(font) => Ok(self.register_face(FontFace::new(family, weight, style, FontArc::from(font)))),
+            Err(_) => Err(ZodiacError::FailedToLoadFont(family.to_string()))
+        }
+    }
+
+    fn register_face(&mut self, face: FontFace) -> FontId {
+        let id = FontId { id: self.faces.len() };
+        self.families.entry(face.family.clone()).or_default().push(id);
+        self.faces.push(face);
+        id
+    }
+
+    pub fn set_fallbacks(&mut self, family: &str, fallbacks: Vec<String>) {
+        self.fallbacks.insert(family.to_string(), fallbacks);
+    }
+
+    pub fn add_default_fallback(&mut self, family: &str) {
+        self.default_fallbacks.push(family.to_string());
+    }
+
+    pub fn face(&self, id: FontId) -> &FontFace {
+        &self.faces[id.id]
+    }
+
+    pub fn faces(&self) -> impl Iterator<Item = (FontId, &FontFace)> {
+        self.faces
+            .iter()
+            .enumerate()
+            .map(|(id, face)| (FontId { id }, face))
+    }
+
+    pub fn resolve(&self, selection: &FontSelection) -> FontId {
+        self.family_chain(selection)
+            .find_map(|family| self.resolve_in_family(family, selection))
+            .unwrap_or_default()
+    }
+
+    pub fn resolve_for_character(&self, selection: &FontSelection, character: char) -> FontId {
+        self.family_chain(selection)
+            .filter_map(|family| self.resolve_in_family(family, selection))
+            .find(|id| self.face(*id).has_glyph(character))
+            .unwrap_or_else(|| self.resolve(selection))
+    }
+
+    fn family_chain<'a>(&'a self, selection: &'a FontSelection) -> impl Iterator<Item = &'a str> {
+        let family = selection.family.as_deref();
+        let fallbacks = family
+            .and_then(|family| self.fallbacks.get(family))
+            .into_iter()
+            .flatten();
+        family
+            .into_iter()
+            .chain(fallbacks.map(|family| family.as_str()))
+            .chain(self.default_fallbacks.iter().map(|family| family.as_str()))
+    }
+
+    fn resolve_in_family(&self, family: &str, selection: &FontSelection) -> Option<FontId> {
+        self.families
+            .get(family)?
+            .iter()
+            .copied()
+            .min_by_key(|id| {
+                let face = self.face(*id);
+                (style_distance(face.style, selection.style), weight_distance(face.weight, selection.weight))
+            })
+    }
+}
+
+fn style_distance(style: FontStyle, requested: FontStyle) -> u8 {
+    match (style, requested) {
+        (style, requested) if style == requested => 0,
+        (FontStyle::Italic, FontStyle::Oblique) | (FontStyle::Oblique, FontStyle::Italic) => 1,
+        _ => 2
+    }
+}
+
+fn weight_distance(weight: FontWeight, requested: FontWeight) -> (u16, bool) {
+    if weight.weight >= requested.weight {
+        (weight.weight - requested.weight, false)
+    } else {
+        (requested.weight - weight.weight, true)
+    }
+}
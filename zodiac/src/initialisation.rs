@@ -1,4 +1,7 @@
 use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
 use log::info;
 use shrev::*;
 use legion::*;
@@ -56,6 +59,254 @@ impl ApplicationBundleBuilder for WorldLoggingBuilder {
     }
 }
 
+pub fn fonts() -> FontsBuilder {
+    FontsBuilder::default()
+}
+
+#[derive(Debug, Clone)]
+enum FontSource {
+    Embedded(&'static [u8]),
+    File(PathBuf)
+}
+
+#[derive(Debug, Clone)]
+struct FontDefinition {
+    family: String,
+    weight: FontWeight,
+    style: FontStyle,
+    source: FontSource
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FontsBuilder {
+    definitions: Vec<FontDefinition>,
+    fallbacks: Vec<(String, Vec<String>)>,
+    default_fallbacks: Vec<String>
+}
+
+impl FontsBuilder {
+    pub fn with_embedded_font(mut self, family: &str, weight: FontWeight, style: FontStyle, data: &'static [u8]) -> Self {
+        self.definitions.push(FontDefinition { family: family.to_string(), weight, style, source: FontSource::Embedded(data) });
+        self
+    }
+
+    pub fn with_font_file<P: Into<PathBuf>>(mut self, family: &str, weight: FontWeight, style: FontStyle, path: P) -> Self {
+        self.definitions.push(FontDefinition { family: family.to_string(), weight, style, source: FontSource::File(path.into()) });
+        self
+    }
+
+    pub fn with_fallbacks(mut self, family: &str, fallbacks: &[&str]) -> Self {
+        self.fallbacks.push((family.to_string(), fallbacks.iter().map(|fallback| fallback.to_string()).collect()));
+        self
+    }
+
+    pub fn with_default_fallback(mut self, family: &str) -> Self {
+        self.default_fallbacks.push(family.to_string());
+        self
+    }
+}
+
+impl ApplicationBundleBuilder for FontsBuilder {
+    fn description(&self) -> String {
+        "fonts".to_string()
+    }
+
+    fn setup_build_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_layout_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_rendering_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_cleanup_systems(&self, _: &mut Builder) {            
+    }
+
+    fn setup_final_functions(&self, _: &mut Builder) {
+    }
+
+    fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
+        register_fonts(resources, |registry| {
+            for definition in &self.definitions {
+                match &definition.source {
+                    FontSource::Embedded(data) => registry.register_embedded(&definition.family, definition.weight, definition.style, *data)?,
+                    FontSource::File(path) => registry.register_file(&definition.family, definition.weight, definition.style, path)?
+                };
+            }
+            for (family, fallbacks) in &self.fallbacks {
+                registry.set_fallbacks(family, fallbacks.clone());
+            }
+            for family in &self.default_fallbacks {
+                registry.add_default_fallback(family);
+            }
+            Ok(())
+        })
+    }
+
+    fn register_components_for_world_serializiation(&self, _: &mut WorldSerializer) {
+    }
+}
+
+pub fn clipboard<TClipboard: Clipboard + 'static>(clipboard: TClipboard) -> ClipboardBuilder {
+    ClipboardBuilder::new(ApplicationClipboard::new(clipboard))
+}
+
+#[derive(Clone)]
+pub struct ClipboardBuilder {
+    clipboard: ApplicationClipboard
+}
+
+impl ClipboardBuilder {
+    pub fn new(clipboard: ApplicationClipboard) -> Self {
+        Self {
+            clipboard
+        }
+    }
+}
+
+impl ApplicationBundleBuilder for ClipboardBuilder {
+    fn description(&self) -> String {
+        "clipboard".to_string()
+    }
+
+    fn setup_build_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_layout_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_rendering_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_cleanup_systems(&self, _: &mut Builder) {            
+    }
+
+    fn setup_final_functions(&self, _: &mut Builder) {
+    }
+
+    fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
+        resources.insert(self.clipboard.clone());
+        Ok(())
+    }
+
+    fn register_components_for_world_serializiation(&self, _: &mut WorldSerializer) {
+    }
+}
+
+pub fn localisation(locale: &str) -> LocalisationBuilder {
+    LocalisationBuilder::new(locale)
+}
+
+#[derive(Clone)]
+enum CatalogSource {
+    Embedded(&'static str),
+    File(PathBuf)
+}
+
+#[derive(Clone)]
+pub struct LocalisationBuilder {
+    locale: String,
+    fallback_locale: Option<String>,
+    catalogs: Vec<(String, CatalogSource)>,
+    plural_rules: Vec<(String, PluralRule)>,
+    number_formatters: Vec<(String, NumberFormatter)>,
+    date_formatters: Vec<(String, DateFormatter)>
+}
+
+impl LocalisationBuilder {
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.to_string(),
+            fallback_locale: None,
+            catalogs: vec!(),
+            plural_rules: vec!(),
+            number_formatters: vec!(),
+            date_formatters: vec!()
+        }
+    }
+
+    pub fn with_fallback_locale(mut self, locale: &str) -> Self {
+        self.fallback_locale = Some(locale.to_string());
+        self
+    }
+
+    pub fn with_catalog(mut self, locale: &str, source: &'static str) -> Self {
+        self.catalogs.push((locale.to_string(), CatalogSource::Embedded(source)));
+        self
+    }
+
+    pub fn with_catalog_file<P: Into<PathBuf>>(mut self, locale: &str, path: P) -> Self {
+        self.catalogs.push((locale.to_string(), CatalogSource::File(path.into())));
+        self
+    }
+
+    pub fn with_plural_rule<T: Fn(f64) -> PluralCategory + Send + Sync + 'static>(mut self, locale: &str, rule: T) -> Self {
+        self.plural_rules.push((locale.to_string(), Arc::new(rule)));
+        self
+    }
+
+    pub fn with_number_formatter<T: Fn(f64) -> String + Send + Sync + 'static>(mut self, locale: &str, formatter: T) -> Self {
+        self.number_formatters.push((locale.to_string(), Arc::new(formatter)));
+        self
+    }
+
+    pub fn with_date_formatter<T: Fn(SystemTime) -> String + Send + Sync + 'static>(mut self, locale: &str, formatter: T) -> Self {
+        self.date_formatters.push((locale.to_string(), Arc::new(formatter)));
+        self
+    }
+}
+
+impl ApplicationBundleBuilder for LocalisationBuilder {
+    fn description(&self) -> String {
+        "localisation".to_string()
+    }
+
+    fn setup_build_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_layout_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_rendering_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_cleanup_systems(&self, _: &mut Builder) {            
+    }
+
+    fn setup_final_functions(&self, _: &mut Builder) {
+    }
+
+    fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
+        register_localisation(resources, |localisation| {
+            localisation.set_locale(&self.locale);
+            if let Some(fallback_locale) = &self.fallback_locale {
+                localisation.set_fallback_locale(fallback_locale);
+            }
+            for (locale, source) in &self.catalogs {
+                let catalog = match source {
+                    CatalogSource::Embedded(source) => MessageCatalog::parse(source)?,
+                    CatalogSource::File(path) => MessageCatalog::load(path)?
+                };
+                localisation.add_catalog(locale, catalog);
+            }
+            for (locale, rule) in &self.plural_rules {
+                localisation.set_plural_rule(locale, rule.clone());
+            }
+            for (locale, formatter) in &self.number_formatters {
+                localisation.set_number_formatter(locale, formatter.clone());
+            }
+            for (locale, formatter) in &self.date_formatters {
+                localisation.set_date_formatter(locale, formatter.clone());
+            }
+            Ok(())
+        })
+    }
+
+    fn register_components_for_world_serializiation(&self, _: &mut WorldSerializer) {
+    }
+}
+
 pub fn zodiac_source<TState: State, TRootFunc: FnMut() -> RootNode<TState> +'static>(state: TState, root_func: TRootFunc) -> ZodiacSourceBuilder<TState, TRootFunc>  {
     ZodiacSourceBuilder::new(state, root_func)
 }
@@ -101,8 +352,10 @@ impl<TState: State, TRootFunc: FnMut() -> RootNode<TState> + Copy + Clone + 'sta
     fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
         resources.insert(create_relationship_map());
         resources.insert(create_system_event_producer());     
-        resources.insert(create_moxie_runner::<TState, TRootFunc>(self.root_func, self.state));
+        resources.insert(create_moxie_runner::<TState, TRootFunc>(self.root_func, self.state.clone()));
         resources.insert(create_state_repository::<TState>());
+        resources.insert(create_state_update_queue());
+        register_localisation(resources, |_| Ok(()))?;
         resources.insert(create_entity_map()); 
         Ok(())
     }
@@ -120,17 +373,59 @@ impl<TState: State, TRootFunc: FnMut() -> RootNode<TState> + Copy + Clone + 'sta
         world_serializer.register_component::<Mapped>(stringify!(Mapped));
         world_serializer.register_component::<LayoutType>(stringify!(LayoutType));
         world_serializer.register_component::<LayoutContent>(stringify!(LayoutContent));
+        world_serializer.register_component::<Sizing>(stringify!(Sizing));
+        world_serializer.register_component::<Flow>(stringify!(Flow));
+        world_serializer.register_component::<LineAlignment>(stringify!(LineAlignment));
+        world_serializer.register_component::<LineSpacing>(stringify!(LineSpacing));
+        world_serializer.register_component::<Dock>(stringify!(Dock));
+        world_serializer.register_component::<Alignment>(stringify!(Alignment));
+        world_serializer.register_component::<Direction>(stringify!(Direction));
+        world_serializer.register_component::<CurrentDirection>(stringify!(CurrentDirection));
+        world_serializer.register_component::<Visibility>(stringify!(Visibility));
+        world_serializer.register_component::<Invisible>(stringify!(Invisible));
+        world_serializer.register_component::<LayoutOutline>(stringify!(LayoutOutline));
         world_serializer.register_component::<LayoutRequest>(stringify!(LayoutRequest));
         world_serializer.register_component::<LayoutChange>(stringify!(LayoutChange));
+        world_serializer.register_component::<LayoutDiagnostics>(stringify!(LayoutDiagnostics));
         world_serializer.register_component::<Renderable>(stringify!(Renderable));
+        world_serializer.register_component::<Rows>(stringify!(Rows));
+        world_serializer.register_component::<Columns>(stringify!(Columns));
+        world_serializer.register_component::<RowGap>(stringify!(RowGap));
+        world_serializer.register_component::<ColumnGap>(stringify!(ColumnGap));
+        world_serializer.register_component::<Row>(stringify!(Row));
+        world_serializer.register_component::<Column>(stringify!(Column));
+        world_serializer.register_component::<RowSpan>(stringify!(RowSpan));
+        world_serializer.register_component::<ColumnSpan>(stringify!(ColumnSpan));
         world_serializer.register_component::<Content>(stringify!(Content));
+        world_serializer.register_component::<FontFamily>(stringify!(FontFamily));
+        world_serializer.register_component::<FontWeight>(stringify!(FontWeight));
+        world_serializer.register_component::<FontStyle>(stringify!(FontStyle));
+        world_serializer.register_component::<TextWrap>(stringify!(TextWrap));
+        world_serializer.register_component::<TextAlign>(stringify!(TextAlign));
+        world_serializer.register_component::<LineHeight>(stringify!(LineHeight));
+        world_serializer.register_component::<MaxLines>(stringify!(MaxLines));
+        world_serializer.register_component::<Underline>(stringify!(Underline));
+        world_serializer.register_component::<Background>(stringify!(Background));
+        world_serializer.register_component::<Value>(stringify!(Value));
+        world_serializer.register_component::<Placeholder>(stringify!(Placeholder));
+        world_serializer.register_component::<MultiLine>(stringify!(MultiLine));
+        world_serializer.register_component::<Editable>(stringify!(Editable));
+        world_serializer.register_component::<TextEditState>(stringify!(TextEditState));
+        world_serializer.register_component::<Selectable>(stringify!(Selectable));
+        world_serializer.register_component::<TextSelection>(stringify!(TextSelection));
+        world_serializer.register_component::<TextLines>(stringify!(TextLines));
         world_serializer.register_component::<Left>(stringify!(Left));
         world_serializer.register_component::<Top>(stringify!(Top));
+        world_serializer.register_component::<Right>(stringify!(Right));
+        world_serializer.register_component::<Bottom>(stringify!(Bottom));
+        world_serializer.register_component::<CenterX>(stringify!(CenterX));
+        world_serializer.register_component::<CenterY>(stringify!(CenterY));
         world_serializer.register_component::<OffsetsMapped>(stringify!(OffsetsMapped));
         world_serializer.register_component::<Width>(stringify!(Width));
         world_serializer.register_component::<MinimumWidth>(stringify!(MinimumWidth));
         world_serializer.register_component::<Height>(stringify!(Height));
         world_serializer.register_component::<MinimumHeight>(stringify!(MinimumHeight));
+        world_serializer.register_component::<AspectRatio>(stringify!(AspectRatio));
         world_serializer.register_component::<Radius>(stringify!(Radius));
         world_serializer.register_component::<Colour>(stringify!(Colour));
         world_serializer.register_component::<StrokeWidth>(stringify!(StrokeWidth));
@@ -286,6 +581,20 @@ impl<TState: State> ApplicationRunner<TState> {
         repository.get()
     }
 
+    pub fn set_locale(&mut self, locale: &str) {
+        self.resources
+            .get_mut::<Localisation>()
+            .unwrap()
+            .set_locale(locale);
+    }
+
+    pub fn send_event(&mut self, event: SystemEvent) {
+        self.resources
+            .get_mut::<EventChannel<SystemEvent>>()
+            .unwrap()
+            .single_write(event);
+    }
+
     pub fn resources_mut(&mut self) -> &mut Resources {
         &mut self.resources
     }
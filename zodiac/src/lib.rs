@@ -7,6 +7,10 @@ mod initialisation;
 mod application_state;
 mod source;
 mod rendering;
+mod fonts;
+mod editing;
+mod clipboard;
+mod localisation;
 
 pub use application_state::*;
 pub use source::*;
@@ -21,6 +25,10 @@ pub use systems::running::*;
 pub use systems::world_vision::*;
 pub use systems::cleanup::*;
 pub use rendering::*;
+pub use fonts::*;
+pub use editing::*;
+pub use clipboard::*;
+pub use localisation::*;
 pub use events::*;
 pub use initialisation::*;
 pub use formatting::WorldSerializer;
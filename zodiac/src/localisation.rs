@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{ SystemTime, UNIX_EPOCH };
+use legion::*;
+use crate::*;
+
+pub const DEFAULT_LOCALE: &str = "en";
+pub const PLURAL_ARGUMENT: &str = "count";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other
+}
+
+impl PluralCategory {
+    fn variant(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other"
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationArg {
+    Text(String),
+    Number(f64),
+    Date(SystemTime)
+}
+
+impl From<&str> for TranslationArg {
+    fn from(text: &str) -> Self {
+        TranslationArg::Text(text.to_string())
+    }
+}
+
+impl From<String> for TranslationArg {
+    fn from(text: String) -> Self {
+        TranslationArg::Text(text)
+    }
+}
+
+impl From<&String> for TranslationArg {
+    fn from(text: &String) -> Self {
+        TranslationArg::Text(text.clone())
+    }
+}
+
+impl From<SystemTime> for TranslationArg {
+    fn from(date: SystemTime) -> Self {
+        TranslationArg::Date(date)
+    }
+}
+
+macro_rules! number_translation_arg {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for TranslationArg {
+                fn from(number: $number) -> Self {
+                    TranslationArg::Number(number as f64)
+                }
+            }
+        )*
+    };
+}
+
+number_translation_arg!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+pub type PluralRule = Arc<dyn Fn(f64) -> PluralCategory + Send + Sync>;
+pub type NumberFormatter = Arc<dyn Fn(f64) -> String + Send + Sync>;
+pub type DateFormatter = Arc<dyn Fn(SystemTime) -> String + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MessageCatalog {
+    messages: HashMap<String, String>
+}
+
+impl MessageCatalog {
+    pub fn parse(source: &str) -> Result<Self, ZodiacError> {
+        let mut catalog = Self::default();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.find('=') {
+                Some(separator) if separator > 0 => {
+                    catalog.messages.insert(
+                        line[..separator].trim().to_string(),
+                        line[separator + 1..].trim().replace("\\n", "\n"));
+                },
+                _ => return Err(ZodiacError::FailedToLoadCatalog(format!("line {}: {}", index + 1, line)))
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ZodiacError> {
+        match std::fs::read_to_string(path.as_ref()) {
+            Ok(source) => Self::parse(&source),
+            Err(_) => Err(ZodiacError::FailedToLoadCatalog(path.as_ref().display().to_string()))
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    pub fn merge(&mut self, other: MessageCatalog) {
+        self.messages.extend(other.messages);
+    }
+}
+
+#[derive(Clone, Default)]
+struct LocaleCatalogs {
+    catalogs: HashMap<String, MessageCatalog>,
+    plural_rules: HashMap<String, PluralRule>,
+    number_formatters: HashMap<String, NumberFormatter>,
+    date_formatters: HashMap<String, DateFormatter>,
+    fallback_locale: Option<String>
+}
+
+pub fn create_localisation() -> Localisation {
+    Localisation::new(DEFAULT_LOCALE)
+}
+
+pub fn register_localisation(
+    resources: &mut Resources,
+    register: impl FnOnce(&mut Localisation) -> Result<(), ZodiacError>) -> Result<(), ZodiacError> {
+    if !resources.contains::<Localisation>() {
+        resources.insert(create_localisation());
+    }
+    let mut localisation = resources.get_mut::<Localisation>().unwrap();
+    register(&mut *localisation)
+}
+
+#[derive(Clone)]
+pub struct Localisation {
+    locale: String,
+    catalogs: Arc<LocaleCatalogs>
+}
+
+impl fmt::Debug for Localisation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("Localisation")
+            .field("locale", &self.locale)
+            .field("fallback_locale", &self.catalogs.fallback_locale)
+            .field("catalogs", &self.catalogs.catalogs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Localisation {
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.to_string(),
+            catalogs: Arc::new(LocaleCatalogs::default())
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = locale.to_string();
+    }
+
+    pub fn set_fallback_locale(&mut self, locale: &str) {
+        Arc::make_mut(&mut self.catalogs).fallback_locale = Some(locale.to_string());
+    }
+
+    pub fn add_catalog(&mut self, locale: &str, catalog: MessageCatalog) {
+        Arc::make_mut(&mut self.catalogs).catalogs
+            .entry(locale.to_string())
+            .or_default()
+            .merge(catalog);
+    }
+
+    pub fn set_plural_rule(&mut self, locale: &str, rule: PluralRule) {
+        Arc::make_mut(&mut self.catalogs).plural_rules.insert(locale.to_string(), rule);
+    }
+
+    pub fn set_number_formatter(&mut self, locale: &str, formatter: NumberFormatter) {
+        Arc::make_mut(&mut self.catalogs).number_formatters.insert(locale.to_string(), formatter);
+    }
+
+    pub fn set_date_formatter(&mut self, locale: &str, formatter: DateFormatter) {
+        Arc::make_mut(&mut self.catalogs).date_formatters.insert(locale.to_string(), formatter);
+    }
+
+    pub fn translate(&self, key: &str, args: &[(&str, TranslationArg)]) -> String {
+        let plural_key = args
+            .iter()
+            .find_map(|(name, arg)| match arg {
+                TranslationArg::Number(number) if *name == PLURAL_ARGUMENT => Some(*number),
+                _ => None
+            })
+            .map(|count| format!("{}[{}]", key, self.plural_category(count).variant()));
+
+        let message = self.locale_chain()
+            .iter()
+            .filter_map(|locale| self.catalogs.catalogs.get(locale))
+            .find_map(|catalog| plural_key
+                .as_deref()
+                .and_then(|plural_key| catalog.get(plural_key))
+                .or_else(|| plural_key.as_ref().and_then(|_| catalog.get(&format!("{}[other]", key))))
+                .or_else(|| catalog.get(key)));
+
+        match message {
+            Some(message) => self.substitute(message, args),
+            None => key.to_string()
+        }
+    }
+
+    pub fn plural_category(&self, count: f64) -> PluralCategory {
+        match self.find_for_locale(&self.catalogs.plural_rules) {
+            Some(rule) => rule(count),
+            None => default_plural_category(&language(&self.locale), count)
+        }
+    }
+
+    pub fn format_number(&self, number: f64) -> String {
+        match self.find_for_locale(&self.catalogs.number_formatters) {
+            Some(formatter) => formatter(number),
+            None => default_number_format(number)
+        }
+    }
+
+    pub fn format_date(&self, date: SystemTime) -> String {
+        match self.find_for_locale(&self.catalogs.date_formatters) {
+            Some(formatter) => formatter(date),
+            None => default_date_format(date)
+        }
+    }
+
+    fn find_for_locale<'a, T>(&self, hooks: &'a HashMap<String, T>) -> Option<&'a T> {
+        hooks
+            .get(&self.locale)
+            .or_else(|| hooks.get(&language(&self.locale)))
+    }
+
+    fn locale_chain(&self) -> Vec<String> {
+        let mut chain = vec!(self.locale.clone(), language(&self.locale));
+        if let Some(fallback) = &self.catalogs.fallback_locale {
+            chain.push(fallback.clone());
+            chain.push(language(fallback));
+        }
+        chain.dedup();
+        chain
+    }
+
+    fn substitute(&self, message: &str, args: &[(&str, TranslationArg)]) -> String {
+        let mut translated = String::with_capacity(message.len());
+        let mut remaining = message;
+        while let Some(start) = remaining.find('{') {
+            translated.push_str(&remaining[..start]);
+            let placeable = &remaining[start + 1..];
+            match placeable.find('}') {
+                Some(end) => {
+                    let name = placeable[..end].trim();
+                    match args.iter().find(|(arg_name, _)| *arg_name == name) {
+                        Some((_, arg)) => translated.push_str(&self.format_arg(arg)),
+                        None => translated.push_str(&remaining[start..start + end + 2])
+                    }
+                    remaining = &placeable[end + 1..];
+                },
+                None => {
+                    translated.push_str(&remaining[start..]);
+                    remaining = "";
+                }
+            }
+        }
+        translated.push_str(remaining);
+        translated
+    }
+
+    fn format_arg(&self, arg: &TranslationArg) -> String {
+        match arg {
+            TranslationArg::Text(text) => text.clone(),
+            TranslationArg::Number(number) => self.format_number(*number),
+            TranslationArg::Date(date) => self.format_date(*date)
+        }
+    }
+}
+
+pub fn translate(key: &str, args: &[(&str, TranslationArg)]) -> String {
+    match illicit::get::<Localisation>() {
+        Ok(localisation) => localisation.translate(key, args),
+        Err(_) => key.to_string()
+    }
+}
+
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::translate($key, &[$((stringify!($name), $crate::TranslationArg::from($value))),+])
+    };
+}
+
+fn language(locale: &str) -> String {
+    locale
+        .split(|character| character == '-' || character == '_')
+        .next()
+        .unwrap_or(locale)
+        .to_string()
+}
+
+fn default_plural_category(language: &str, count: f64) -> PluralCategory {
+    let integer = count.fract() == 0.0;
+    let count = count.abs();
+    let ones = count % 10.0;
+    let tens = count % 100.0;
+    match language {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" => PluralCategory::Other,
+        "fr" | "pt" => if count < 2.0 { PluralCategory::One } else { PluralCategory::Other },
+        "ru" | "uk" if integer => {
+            if ones == 1.0 && tens != 11.0 {
+                PluralCategory::One
+            } else if (2.0..=4.0).contains(&ones) && !(12.0..=14.0).contains(&tens) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        },
+        "pl" if integer => {
+            if count == 1.0 {
+                PluralCategory::One
+            } else if (2.0..=4.0).contains(&ones) && !(12.0..=14.0).contains(&tens) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        },
+        "ru" | "uk" | "pl" => PluralCategory::Other,
+        _ => if count == 1.0 && integer { PluralCategory::One } else { PluralCategory::Other }
+    }
+}
+
+fn default_number_format(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        format!("{}", number as i64)
+    } else {
+        format!("{}", number)
+    }
+}
+
+fn default_date_format(date: SystemTime) -> String {
+    let seconds = match date.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs() as i64)
+    };
+    let days = seconds.div_euclid(86_400);
+    let era = (days + 719_468).div_euclid(146_097);
+    let day_of_era = days + 719_468 - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -2,4 +2,16 @@ use crate::Dimensions;
 
 pub trait Renderer {
     fn get_window_dimensions(&self) -> Dimensions;
-}
\ No newline at end of file
+
+    fn get_scale_factor(&self) -> f32 {
+        self.get_window_dimensions().scale_factor
+    }
+}
+
+pub fn rasterise(logical: f32, scale_factor: f32) -> u16 {
+    (logical * scale_factor).round().max(0.0).min(u16::MAX as f32) as u16
+}
+
+pub fn rasterise_font_size(logical: u8, scale_factor: f32) -> u8 {
+    (logical as f32 * scale_factor).round().max(0.0).min(u8::MAX as f32) as u8
+}
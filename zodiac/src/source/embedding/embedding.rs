@@ -7,8 +7,22 @@ element! {
     attributes {
         left(u16)
         top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
         width(u16)
         height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
     }
 }
 
@@ -18,8 +32,22 @@ element! {
     attributes {
         left(u16)
         top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
         width(u16)
         height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
     }
 }
 
@@ -29,7 +57,128 @@ element! {
     attributes {
         left(u16)
         top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
         width(u16)
         height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <grid>
+    [LayoutContent::grid()]
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        rows(Vec<GridTrack>)
+        columns(Vec<GridTrack>)
+        row_gap(u16)
+        column_gap(u16)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <wrap_stack>
+    [LayoutContent::wrap()]
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        flow(Flow)
+        line_spacing(u16)
+        line_alignment(LineAlignment)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <dock>
+    [LayoutContent::dock()]
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <layer_stack>
+    [LayoutContent::layer()]
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        sizing(Sizing)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
     }
 }
\ No newline at end of file
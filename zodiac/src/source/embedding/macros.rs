@@ -113,6 +113,7 @@ macro_rules! element {
             impl SourceBuildChange for [<$name:camel Change>] {
                 fn apply<'a>(&self, command_buffer: &mut legion::systems::CommandBuffer, maps: &mut SourceBuildMaps<'a>) {        
                     let parent = command_buffer.get_or_create(self.node_id, || $component, maps);
+                    command_buffer.add_component(parent, Rebuild::default());
                     $($(
                     command_buffer.add_component(parent, $extra_component);
                     )*)?
@@ -120,21 +121,21 @@ macro_rules! element {
                     self.child_changes.process_additions(&mut |child_id| command_buffer.add_child(parent, child_id, maps));    
                     self.child_changes.process_removals(&mut |child_id| command_buffer.remove_child(child_id, maps));
                     
-                    self.attribute_changes.process_additions(&mut |attribute| {
+                    self.attribute_changes.process_removals(&mut |attribute| {
                         match attribute {
                             $($(
-                            [<$name:camel Attribute>]::[<$attr:camel>](value) => 
-                                command_buffer.add_component(parent, [<$attr:camel>]::from(value.clone())),
+                            [<$name:camel Attribute>]::[<$attr:camel>](_) => 
+                                command_buffer.remove_component::<[<$attr:camel>]>(parent),
                             )*)?
                             [<$name:camel Attribute>]::None => {}
                         }
-                    });    
-            
-                    self.attribute_changes.process_removals(&mut |attribute| {
+                    });
+
+                    self.attribute_changes.process_additions(&mut |attribute| {
                         match attribute {
                             $($(
-                            [<$name:camel Attribute>]::[<$attr:camel>](_) => 
-                                command_buffer.remove_component::<[<$attr:camel>]>(parent),
+                            [<$name:camel Attribute>]::[<$attr:camel>](value) => 
+                                command_buffer.add_component(parent, [<$attr:camel>]::from(value.clone())),
                             )*)?
                             [<$name:camel Attribute>]::None => {}
                         }
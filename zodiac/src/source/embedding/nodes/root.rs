@@ -9,7 +9,7 @@ use super::nodes::*;
 pub struct RootNode<TState: State> {
     id: u64,
     changes: SourceBuildChanges,
-    state_snapshot: TState
+    state_snapshot: Option<TState>
 }
 
 impl<TState: State> RootNode<TState> {
@@ -17,19 +17,26 @@ impl<TState: State> RootNode<TState> {
         Self {
             id: generate_node_id(),
             changes: SourceBuildChanges::default(),
-            state_snapshot: TState::default()
+            state_snapshot: None
         }
     }
 
-    pub fn changes(self) -> (SourceBuildChanges, TState) {
+    pub fn changes(self) -> (SourceBuildChanges, Option<TState>) {
         (self.changes, self.state_snapshot)
     }
 
+    pub fn snapshot_state(&mut self, state: TState) {
+        self.state_snapshot = Some(state);
+    }
+
     #[from_env(changes: &Key<SourceBuildChangeState>)]
+    fn collect_changes(&mut self) {
+        self.changes = changes.commit();
+    }
+
     #[from_env(app_state: &Key<TState>)]
     fn collect_state(&mut self) {
-        self.changes = changes.commit();
-        self.state_snapshot = **app_state;
+        self.state_snapshot = Some((**app_state).clone());
     }
 }
 
@@ -61,6 +68,7 @@ impl<TState: State> RootBuilder<TState> {
         );
 
         if state.has_changed() {
+            node.collect_changes();
             node.collect_state();
         }
 
@@ -106,6 +114,7 @@ impl<TState: State> SourceBuildChange for RootChange<TState> {
     fn apply<'a>(&self, command_buffer: &mut CommandBuffer, maps: &mut SourceBuildMaps<'a>) {        
         let parent = command_buffer.get_or_create(self.node_id, || Root::default(), maps);
         command_buffer.add_component(parent, LayoutContent::canvas());
+        command_buffer.add_component(parent, Rebuild::default());
         self.child_changes.process_additions(&mut |child_id| command_buffer.add_child(parent, child_id, maps));    
         self.child_changes.process_removals(&mut |child_id| command_buffer.remove_child(child_id, maps));
     }
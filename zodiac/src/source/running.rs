@@ -1,4 +1,6 @@
 
+use std::cell::RefCell;
+use std::rc::Rc;
 use moxie::*;
 use moxie::runtime::Runtime as MoxieRuntime;
 use illicit::*;
@@ -12,26 +14,59 @@ pub fn create_moxie_runner<TState: State, TRootFunc: FnMut() -> RootNode<TState>
 }
 pub struct MoxieRunner<TState: State> {
     root_func: Box::<dyn FnMut() -> RootNode<TState>>,
-    runtime: MoxieRuntime
+    runtime: MoxieRuntime,
+    pending_updates: Rc<RefCell<Vec<StateUpdate>>>,
+    localisation: Rc<RefCell<Localisation>>
 }
 
 impl<TState: State> MoxieRunner<TState> {
     fn new<TRootFunc: FnMut() -> RootNode<TState> +'static>(
         mut root_func: TRootFunc,
         default_state: TState) -> Self {
+        let pending_updates = Rc::new(RefCell::new(Vec::<StateUpdate>::new()));
+        let root_updates = pending_updates.clone();
+        let localisation = Rc::new(RefCell::new(create_localisation()));
+        let root_localisation = localisation.clone();
         Self {
             root_func: Box::new(move || {
-                Layer::new()
-                    .offer(state(|| default_state.clone()).1)
+                let app_state = state(|| default_state.clone()).1;
+                let updated_state = apply_state_updates(&app_state, root_updates.borrow_mut().drain(..).collect());
+                let mut root_node = Layer::new()
+                    .offer(app_state)
+                    .offer(root_localisation.borrow().clone())
                     .offer(state(|| NodeBuildCache::default()).1)
                     .offer(state(|| SourceBuildChangeState::default()).1)
-                    .enter(|| topo::root(|| root_func()))
+                    .enter(|| topo::root(|| root_func()));
+                if let Some(updated_state) = updated_state {
+                    root_node.snapshot_state(updated_state);
+                }
+                root_node
             }),
-            runtime: MoxieRuntime::new()
+            runtime: MoxieRuntime::new(),
+            pending_updates,
+            localisation
         }
     }    
 
-    pub fn run_once(&mut self) -> RootNode<TState> {
+    pub fn run_once(&mut self, updates: Vec<StateUpdate>, localisation: &Localisation) -> RootNode<TState> {
+        self.pending_updates.borrow_mut().extend(updates);
+        *self.localisation.borrow_mut() = localisation.clone();
         self.runtime.run_once(&mut self.root_func)
     }   
+}
+
+fn apply_state_updates<TState: State>(app_state: &Key<TState>, updates: Vec<StateUpdate>) -> Option<TState> {
+    if updates.is_empty() {
+        return None;
+    }
+    let mut snapshot = None;
+    app_state.update(|current| {
+        let mut updated = current.clone();
+        for update in updates {
+            update(&mut updated);
+        }
+        snapshot = Some(updated.clone());
+        Some(updated)
+    });
+    snapshot
 }
\ No newline at end of file
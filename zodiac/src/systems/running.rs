@@ -9,10 +9,12 @@ pub fn run_moxie<TState: State>(
     command_buffer: &mut CommandBuffer,
     #[resource] moxie_runner: &mut MoxieRunner<TState>,
     #[resource] state_repository: &mut StateRepository<TState>, 
+    #[resource] state_updates: &mut StateUpdateQueue, 
+    #[resource] localisation: &Localisation, 
     #[resource] relationship_map: &mut RelationshipMap, 
     #[resource] entity_map: &mut EntityMap) {
 
-    let root_node = moxie_runner.run_once();
+    let root_node = moxie_runner.run_once(state_updates.take(), localisation);
     
     let mut maps = SourceBuildMaps {
         entity_map,
@@ -21,5 +23,7 @@ pub fn run_moxie<TState: State>(
 
     let (changes, state_snapshot) = root_node.changes();
     changes.apply(command_buffer, &mut maps);
-    state_repository.set(state_snapshot);
+    if let Some(state_snapshot) = state_snapshot {
+        state_repository.set(state_snapshot);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,255 @@
+use std::collections::{ HashMap };
+use criterion::*;
+use legion::*;
+use legion::systems::*;
+use zodiac::*;
+use zodiac_layout::*;
+
+const BRANCH_SIZE: usize = 50;
+const TREE_SIZES: [usize; 3] = [1_000, 10_000, 50_000];
+
+struct LayoutTree {
+    world: World,
+    root: Entity,
+    relationship_map: RelationshipMap,
+    store: LayoutNodeStore
+}
+
+fn relate(relationships: &mut HashMap<Entity, Relationship>, parent: Entity, children: &[Entity]) {
+    let parent_relationship = relationships.entry(parent).or_default();
+    parent_relationship.first_child = children.first().copied();
+    parent_relationship.last_child = children.last().copied();
+
+    for (index, child) in children.iter().enumerate() {
+        let child_relationship = relationships.entry(*child).or_default();
+        child_relationship.parent = Some(parent);
+        child_relationship.previous_sibling = if index > 0 { Some(children[index - 1]) } else { None };
+        child_relationship.next_sibling = children.get(index + 1).copied();
+    }
+}
+
+fn build_tree(size: usize) -> LayoutTree {
+    let mut world = World::default();
+    let mut relationships = HashMap::new();
+    let mut store = create_layout_node_store();
+
+    let root = world.push((Root::default(), Relationship::default()));
+    store.get_or_insert(root).layout_type = Some(LayoutType::Horizontal);
+
+    let mut branches = vec!();
+    for _ in 0..size / BRANCH_SIZE {
+        let branch = world.push((Relationship::default(),));
+        let slot = store.slot_or_insert(branch);
+        store.node_mut(slot).layout_type = Some(LayoutType::Vertical);
+        store.sizes_mut(slot).sizing = Sizing::WrapContent;
+
+        let leaves: Vec<Entity> = (0..BRANCH_SIZE - 1)
+            .map(|index| {
+                let leaf = world.push((Relationship::default(),));
+                let slot = store.slot_or_insert(leaf);
+                let sizes = store.sizes_mut(slot);
+                sizes.width = Some(10.0 + index as f32);
+                sizes.height = Some(20.0);
+                leaf
+            })
+            .collect();
+
+        relate(&mut relationships, branch, &leaves);
+        branches.push(branch);
+    }
+
+    relate(&mut relationships, root, &branches);
+
+    let mut relationship_map = create_relationship_map();
+    for (entity, relationship) in relationships {
+        relationship_map.insert(entity, relationship);
+    }
+
+    LayoutTree { world, root, relationship_map, store }
+}
+
+#[derive(Default)]
+struct PropertyMaps {
+    layout_map: HashMap<Entity, LayoutType>,
+    sizing_map: HashMap<Entity, Sizing>,
+    width_map: HashMap<Entity, f32>,
+    height_map: HashMap<Entity, f32>,
+    minimum_width_map: HashMap<Entity, f32>,
+    minimum_height_map: HashMap<Entity, f32>
+}
+
+impl PropertyMaps {
+    fn from_tree(tree: &LayoutTree, entity: &Entity, maps: &mut PropertyMaps) {
+        if let Some(slot) = tree.store.slot(entity) {
+            let node = tree.store.node(slot);
+            let sizes = tree.store.sizes(slot);
+            if let Some(layout_type) = node.layout_type {
+                maps.layout_map.insert(*entity, layout_type);
+            }
+            maps.sizing_map.insert(*entity, sizes.sizing);
+            if let Some(width) = sizes.width {
+                maps.width_map.insert(*entity, width);
+            }
+            if let Some(height) = sizes.height {
+                maps.height_map.insert(*entity, height);
+            }
+        }
+        for child in tree.relationship_map.get_children(entity) {
+            PropertyMaps::from_tree(tree, &child, maps);
+        }
+    }
+
+    fn measure(&mut self, relationship_map: &RelationshipMap, entity: &Entity, axis: LayoutAxis) -> f32 {
+        let child_sizes: Vec<f32> = relationship_map
+            .get_children(entity)
+            .map(|child| self.measure(relationship_map, &child, axis))
+            .collect();
+
+        let (fixed_map, minimum_map) = match axis {
+            LayoutAxis::Horizontal => (&self.width_map, &mut self.minimum_width_map),
+            LayoutAxis::Vertical => (&self.height_map, &mut self.minimum_height_map)
+        };
+
+        let sums = match (self.layout_map.get(entity), axis) {
+            (Some(LayoutType::Horizontal), LayoutAxis::Horizontal) => true,
+            (Some(LayoutType::Vertical), LayoutAxis::Vertical) => true,
+            _ => false
+        };
+
+        let mut minimum_size = if self.sizing_map.get(entity) == Some(&Sizing::WrapContent) && !sums {
+            child_sizes.iter().copied().fold(0.0, f32::max)
+        } else {
+            child_sizes.iter().sum()
+        };
+        if let Some(size) = fixed_map.get(entity) {
+            minimum_size = *size;
+        }
+        if minimum_size > 0.0 {
+            minimum_map.insert(*entity, minimum_size);
+        } else {
+            minimum_map.remove(entity);
+        }
+        minimum_size
+    }
+
+    fn layout(
+        &self,
+        relationship_map: &RelationshipMap,
+        command_buffer: &mut CommandBuffer,
+        layout_boxes: &mut HashMap<Entity, LayoutConstraints>,
+        entity: &Entity,
+        constraints: LayoutConstraints) {
+        layout_boxes.insert(*entity, constraints);
+        command_buffer.add_component(*entity, CurrentLayoutConstraints::from(&constraints));
+
+        let horizontal = match self.layout_map.get(entity) {
+            Some(LayoutType::Horizontal) => true,
+            Some(_) => false,
+            None => {
+                command_buffer.add_component(*entity, LayoutChange::from(&constraints));
+                command_buffer.add_component(*entity, CurrentDirection { direction: Direction::LeftToRight });
+                command_buffer.remove_component::<Invisible>(*entity);
+                return;
+            }
+        };
+        command_buffer.add_component(*entity, LayoutDiagnostics::default());
+
+        let children: Vec<Entity> = relationship_map.get_children(entity).collect();
+        let minimum_map = if horizontal { &self.minimum_width_map } else { &self.minimum_height_map };
+        let total_fixed_size: f32 = children.iter().filter_map(|child| minimum_map.get(child)).sum();
+        let unspecified = children.iter().filter(|child| !minimum_map.contains_key(child)).count().max(1);
+        let available = if horizontal { constraints.width } else { constraints.height };
+        let share = (available - total_fixed_size).max(0.0) / unspecified as f32;
+
+        let mut offset = 0.0;
+        for child in children {
+            let size = minimum_map.get(&child).copied().unwrap_or(share).min((available - offset).max(0.0));
+            let child_constraints = if horizontal {
+                LayoutConstraints { left: constraints.left + offset, width: size, ..constraints }
+            } else {
+                LayoutConstraints { top: constraints.top + offset, height: size, ..constraints }
+            };
+            offset += size;
+            self.layout(relationship_map, command_buffer, layout_boxes, &child, child_constraints);
+        }
+    }
+}
+
+fn measure_with_store(tree: &mut LayoutTree, algorithms: &LayoutAlgorithmRegistry) {
+    measure_subtree(&tree.relationship_map, algorithms, &mut tree.store, &tree.root, LayoutAxis::Horizontal);
+    measure_subtree(&tree.relationship_map, algorithms, &mut tree.store, &tree.root, LayoutAxis::Vertical);
+}
+
+fn measurement_benchmark(criterion: &mut Criterion) {
+    let algorithms = create_layout_algorithm_registry();
+    let mut group = criterion.benchmark_group("measurement");
+
+    for size in TREE_SIZES.iter() {
+        let mut tree = build_tree(*size);
+        let mut maps = PropertyMaps::default();
+        PropertyMaps::from_tree(&tree, &tree.root, &mut maps);
+
+        group.bench_with_input(BenchmarkId::new("property_maps", size), size, |bencher, _| {
+            bencher.iter(|| {
+                maps.measure(&tree.relationship_map, &tree.root, LayoutAxis::Horizontal);
+                maps.measure(&tree.relationship_map, &tree.root, LayoutAxis::Vertical);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("node_store", size), size, |bencher, _| {
+            bencher.iter(|| measure_with_store(&mut tree, &algorithms))
+        });
+    }
+
+    group.finish();
+}
+
+fn layout_benchmark(criterion: &mut Criterion) {
+    let algorithms = create_layout_algorithm_registry();
+    let dirty_set = create_layout_dirty_set();
+    let mut group = criterion.benchmark_group("layout");
+
+    for size in TREE_SIZES.iter() {
+        let mut tree = build_tree(*size);
+        measure_with_store(&mut tree, &algorithms);
+        let mut maps = PropertyMaps::default();
+        PropertyMaps::from_tree(&tree, &tree.root, &mut maps);
+        maps.measure(&tree.relationship_map, &tree.root, LayoutAxis::Horizontal);
+        maps.measure(&tree.relationship_map, &tree.root, LayoutAxis::Vertical);
+        let constraints = LayoutConstraints { left: 0.0, top: 0.0, width: 1024.0, height: 768.0 };
+
+        group.bench_with_input(BenchmarkId::new("property_maps", size), size, |bencher, _| {
+            bencher.iter(|| {
+                let mut command_buffer = CommandBuffer::new(&tree.world);
+                let mut layout_boxes = HashMap::new();
+                maps.layout(&tree.relationship_map, &mut command_buffer, &mut layout_boxes, &tree.root, constraints);
+                command_buffer.flush(&mut tree.world);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("node_store", size), size, |bencher, _| {
+            bencher.iter(|| {
+                let mut command_buffer = CommandBuffer::new(&tree.world);
+                let mut layout_boxes = create_layout_box_map();
+                let maps = LayoutMaps {
+                    relationship_map: &tree.relationship_map,
+                    store: &tree.store,
+                    algorithms: &algorithms,
+                    dirty_set: &dirty_set
+                };
+                perform_layout(
+                    &maps,
+                    &mut command_buffer,
+                    &mut layout_boxes,
+                    &tree.root,
+                    &constraints);
+                command_buffer.flush(&mut tree.world);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, measurement_benchmark, layout_benchmark);
+criterion_main!(benches);
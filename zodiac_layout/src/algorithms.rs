@@ -0,0 +1,90 @@
+use std::collections::{ HashMap };
+use std::sync::Arc;
+use legion::*;
+use zodiac::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+use crate::canvas::*;
+use crate::stacks::*;
+use crate::grid::*;
+use crate::wrapping::*;
+use crate::docking::*;
+use crate::layering::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutAxis {
+    Horizontal,
+    Vertical
+}
+
+impl LayoutAxis {
+    pub fn cross(&self) -> LayoutAxis {
+        match self {
+            LayoutAxis::Horizontal => LayoutAxis::Vertical,
+            LayoutAxis::Vertical => LayoutAxis::Horizontal
+        }
+    }
+}
+
+pub trait LayoutAlgorithm: Send + Sync {
+    fn measure(&self, maps: &MeasurementMaps, entity: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32;
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement;
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Arrangement {
+    pub children: Vec<(Entity, LayoutConstraints)>,
+    pub diagnostics: Option<LayoutDiagnostics>
+}
+
+impl Arrangement {
+    pub fn new(children: Vec<(Entity, LayoutConstraints)>) -> Self {
+        Self {
+            children,
+            diagnostics: None
+        }
+    }
+
+    pub fn with_diagnostics(mut self, diagnostics: LayoutDiagnostics) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+}
+
+pub fn sum_of_sizes(child_sizes: &[(Entity, f32)]) -> f32 {
+    child_sizes.iter().map(|(_, size)| size).sum()
+}
+
+pub fn max_of_sizes(child_sizes: &[(Entity, f32)]) -> f32 {
+    child_sizes.iter().map(|(_, size)| *size).fold(0.0, f32::max)
+}
+
+pub fn create_layout_algorithm_registry() -> LayoutAlgorithmRegistry {
+    let mut registry = LayoutAlgorithmRegistry::default();
+    registry.register(LayoutType::Canvas, CanvasLayout::default());
+    registry.register(LayoutType::Horizontal, HorizontalLayout::default());
+    registry.register(LayoutType::Vertical, VerticalLayout::default());
+    registry.register(LayoutType::Grid, GridLayout::default());
+    registry.register(LayoutType::Wrap, WrapLayout::default());
+    registry.register(LayoutType::Dock, DockLayout::default());
+    registry.register(LayoutType::Layer, LayerLayout::default());
+    registry
+}
+
+#[derive(Default, Clone)]
+pub struct LayoutAlgorithmRegistry(HashMap<LayoutType, Arc<dyn LayoutAlgorithm>>);
+
+impl LayoutAlgorithmRegistry {
+    pub fn register<T: LayoutAlgorithm + 'static>(&mut self, layout_type: LayoutType, algorithm: T) {
+        self.register_shared(layout_type, Arc::new(algorithm));
+    }
+
+    pub fn register_shared(&mut self, layout_type: LayoutType, algorithm: Arc<dyn LayoutAlgorithm>) {
+        self.0.insert(layout_type, algorithm);
+    }
+
+    pub fn get(&self, layout_type: &LayoutType) -> Option<&dyn LayoutAlgorithm> {
+        self.0.get(layout_type).map(|algorithm| algorithm.as_ref())
+    }
+}
@@ -0,0 +1,27 @@
+use legion::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct CanvasLayout {
+}
+
+impl LayoutAlgorithm for CanvasLayout {
+    fn measure(&self, _: &MeasurementMaps, _: &Entity, _: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        max_of_sizes(child_sizes)
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let mut new_constraints = *constraints;
+        if let Some(node) = maps.store.get(entity) {
+            new_constraints.left += node.left.unwrap_or_default();
+            new_constraints.top += node.top.unwrap_or_default();
+        }
+        Arrangement::new(maps
+            .children(entity)
+            .map(|child| (child, new_constraints))
+            .collect())
+    }
+}
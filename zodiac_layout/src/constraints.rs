@@ -1,24 +1,32 @@
 use std::ops::Add;
 use legion::*;
 use zodiac::*;
-use crate::measurement::*;
+use crate::algorithms::*;
+use crate::store::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LayoutConstraints {
-    pub left: u16,
-    pub top: u16,
-    pub width: u16,
-    pub height: u16
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32
 }
 
 impl LayoutConstraints {
-    pub fn into_width_subdivider<'a>(&self, minimum_width_map: &'a MinimumWidthMap) -> LayoutConstraintsSubDivider<LayoutConstraintsWidthResizer<'a>> {
+    pub fn into_width_subdivider<'a>(&self, store: &'a LayoutNodeStore) -> LayoutConstraintsSubDivider<LayoutConstraintsWidthResizer<'a>> {
         LayoutConstraintsSubDivider::<LayoutConstraintsWidthResizer<'a>>
-            ::from_resizer(LayoutConstraintsWidthResizer(*self, minimum_width_map))
+            ::from_resizer(LayoutConstraintsWidthResizer(*self, store))
     }
-    pub fn into_height_subdivider<'a>(&self, minimum_height_map: &'a MinimumHeightMap) -> LayoutConstraintsSubDivider<LayoutConstraintsHeightResizer<'a>> {
+    pub fn into_height_subdivider<'a>(&self, store: &'a LayoutNodeStore) -> LayoutConstraintsSubDivider<LayoutConstraintsHeightResizer<'a>> {
         LayoutConstraintsSubDivider::<LayoutConstraintsHeightResizer<'a>>
-            ::from_resizer(LayoutConstraintsHeightResizer(*self, minimum_height_map))
+            ::from_resizer(LayoutConstraintsHeightResizer(*self, store))
+    }
+
+    pub fn mirror_horizontally(&self, container: &LayoutConstraints) -> LayoutConstraints {
+        LayoutConstraints {
+            left: container.left + container.width - (self.left - container.left) - self.width,
+            ..*self
+        }
     }
 }
 
@@ -57,6 +65,17 @@ impl Add<Top> for LayoutConstraints {
     }
 }
 
+impl From<&CurrentLayoutConstraints> for LayoutConstraints {
+    fn from(current_layout_constraints: &CurrentLayoutConstraints) -> Self {
+        LayoutConstraints {
+            left: current_layout_constraints.left,
+            top: current_layout_constraints.top,
+            width: current_layout_constraints.width,
+            height: current_layout_constraints.height
+        }
+    }
+}
+
 impl From<&LayoutRequest> for LayoutConstraints {
     fn from(request: &LayoutRequest) -> Self {
         LayoutConstraints {
@@ -70,19 +89,19 @@ impl From<&LayoutRequest> for LayoutConstraints {
 
 enum LayoutConstraintsSubDivisionType {
     SizeNotSpecified(Entity),
-    FixedSizeSpecified(Entity, u16)
+    FixedSizeSpecified(Entity, f32)
 }
 
 pub trait LayoutConstraintsResizer {
-    fn resize(&self, culmative_size: u16, size: u16) -> LayoutConstraints;
-    fn get_size(&self) -> u16;
-    fn get_minimum_size(&self, entity: &Entity) -> Option<u16>;
+    fn resize(&self, culmative_size: f32, size: f32) -> LayoutConstraints;
+    fn get_size(&self) -> f32;
+    fn get_minimum_size(&self, slot: LayoutSlot) -> Option<f32>;
 }
 
-pub struct LayoutConstraintsWidthResizer<'a> (LayoutConstraints, &'a MinimumWidthMap);
+pub struct LayoutConstraintsWidthResizer<'a> (LayoutConstraints, &'a LayoutNodeStore);
 
 impl<'a> LayoutConstraintsResizer for LayoutConstraintsWidthResizer<'a>  {
-    fn resize(&self, culmative_size: u16, size: u16) -> LayoutConstraints {
+    fn resize(&self, culmative_size: f32, size: f32) -> LayoutConstraints {
         LayoutConstraints {
             left: self.0.left + culmative_size,
             top: self.0.top,
@@ -91,22 +110,19 @@ impl<'a> LayoutConstraintsResizer for LayoutConstraintsWidthResizer<'a>  {
         }
     }
 
-    fn get_size(&self) -> u16 {
+    fn get_size(&self) -> f32 {
         self.0.width
     }
 
-    fn get_minimum_size(&self, entity: &Entity) -> Option<u16> {
-        if let Some(width) = self.1.get(entity) {
-            return Some(width.width);
-        }
-        None
+    fn get_minimum_size(&self, slot: LayoutSlot) -> Option<f32> {
+        minimum_or_aspect_size(self.1, slot, LayoutAxis::Horizontal, self.0.height)
     }
 }
 
-pub struct LayoutConstraintsHeightResizer<'a> (LayoutConstraints, &'a MinimumHeightMap);
+pub struct LayoutConstraintsHeightResizer<'a> (LayoutConstraints, &'a LayoutNodeStore);
 
 impl<'a> LayoutConstraintsResizer for LayoutConstraintsHeightResizer<'a> {
-    fn resize(&self, culmative_size: u16, size: u16) -> LayoutConstraints {
+    fn resize(&self, culmative_size: f32, size: f32) -> LayoutConstraints {
         LayoutConstraints {
             left: self.0.left,
             top: self.0.top + culmative_size,
@@ -115,22 +131,28 @@ impl<'a> LayoutConstraintsResizer for LayoutConstraintsHeightResizer<'a> {
         }
     }
 
-    fn get_size(&self) -> u16 {
+    fn get_size(&self) -> f32 {
         self.0.height
     }
 
-    fn get_minimum_size(&self, entity: &Entity) -> Option<u16> {
-        if let Some(height) = self.1.get(entity) {
-            return Some(height.height);
-        }
-        None
+    fn get_minimum_size(&self, slot: LayoutSlot) -> Option<f32> {
+        minimum_or_aspect_size(self.1, slot, LayoutAxis::Vertical, self.0.width)
     }
 }
 
+fn minimum_or_aspect_size(store: &LayoutNodeStore, slot: LayoutSlot, axis: LayoutAxis, cross_size: f32) -> Option<f32> {
+    store.sizes(slot).minimum_size(axis).or_else(|| {
+        store.node(slot).aspect_ratio.map(|aspect_ratio| match axis {
+            LayoutAxis::Horizontal => aspect_ratio.width_for(cross_size),
+            LayoutAxis::Vertical => aspect_ratio.height_for(cross_size)
+        })
+    })
+}
+
 pub struct LayoutConstraintsSubDivider<TResizer: LayoutConstraintsResizer> {
     resizer: TResizer,
     subdivisions: Vec::<LayoutConstraintsSubDivisionType>,
-    total_fixed_size: u16,
+    total_fixed_size: f32,
     total_no_specified_size_items: usize
 }
 
@@ -139,13 +161,13 @@ impl <TResizer> LayoutConstraintsSubDivider<TResizer>  where TResizer: LayoutCon
         LayoutConstraintsSubDivider::<TResizer> {
             resizer,
             subdivisions: vec!(),
-            total_fixed_size: 0, 
+            total_fixed_size: 0.0, 
             total_no_specified_size_items: 0
         }
     }
 
-    pub fn subdivide_for_entity(&mut self, entity: &Entity) {
-        match self.resizer.get_minimum_size(entity) {
+    pub fn subdivide_for_entity(&mut self, entity: &Entity, slot: LayoutSlot) {
+        match self.resizer.get_minimum_size(slot) {
             Some(size) => {
                 self.subdivisions.push(LayoutConstraintsSubDivisionType::FixedSizeSpecified(*entity, size));
                 self.total_fixed_size += size;
@@ -161,29 +183,34 @@ impl <TResizer> LayoutConstraintsSubDivider<TResizer>  where TResizer: LayoutCon
         LayoutConstraintsSubDividerIterator::<TResizer> {
             subdivider: self,
             current_index: 0,
-            culmative_size: 0
+            culmative_size: 0.0
         }
     }
 
+    pub fn overflow(&self) -> f32 {
+        (self.total_fixed_size - self.resizer.get_size()).max(0.0)
+    }
+
     fn get_subdivision(&self, index: usize) -> Option<&LayoutConstraintsSubDivisionType> {
         self.subdivisions.get(index)
     }
 
-    fn slice(&self, culmative_size: u16) -> (LayoutConstraints, u16) {
-        let size = (self.resizer.get_size() - self.total_fixed_size) / self.total_no_specified_size_items as u16;
-        let slice = self.fixed_slice(culmative_size, size);
-        (slice, size)
+    fn slice(&self, culmative_size: f32) -> (LayoutConstraints, f32) {
+        let remaining_size = (self.resizer.get_size() - self.total_fixed_size).max(0.0);
+        let size = remaining_size / self.total_no_specified_size_items.max(1) as f32;
+        self.fixed_slice(culmative_size, size)
     }
     
-    fn fixed_slice(&self, culmative_size: u16, size: u16) -> LayoutConstraints {
-        self.resizer.resize(culmative_size, size)
+    fn fixed_slice(&self, culmative_size: f32, size: f32) -> (LayoutConstraints, f32) {
+        let size = size.min((self.resizer.get_size() - culmative_size).max(0.0));
+        (self.resizer.resize(culmative_size, size), size)
     }
 }
 
 pub struct LayoutConstraintsSubDividerIterator<'a, TResizer: LayoutConstraintsResizer> {
     subdivider: &'a LayoutConstraintsSubDivider<TResizer>,
     current_index: usize,
-    culmative_size: u16
+    culmative_size: f32
 }
 
 impl<'a, TResizer> Iterator for LayoutConstraintsSubDividerIterator<'a, TResizer>  where TResizer: LayoutConstraintsResizer{
@@ -197,7 +224,7 @@ impl<'a, TResizer> Iterator for LayoutConstraintsSubDividerIterator<'a, TResizer
                     Some((*entity, new_constraints))
                 },
                 LayoutConstraintsSubDivisionType::FixedSizeSpecified(entity, size) => {
-                    let new_constraints = self.subdivider.fixed_slice(self.culmative_size, *size);
+                    let (new_constraints, size) = self.subdivider.fixed_slice(self.culmative_size, *size);
                     self.culmative_size += size;
                     Some((*entity, new_constraints))
                 }
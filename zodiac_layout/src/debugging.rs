@@ -0,0 +1,84 @@
+use std::collections::{ HashMap };
+use std::fmt::Write;
+use legion::*;
+use zodiac::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutlinedBox {
+    pub outline: Entity,
+    pub change: LayoutChange,
+    pub colour: StrokeColour,
+    pub width: StrokeWidth
+}
+
+pub type LayoutOutlineMap = HashMap<Entity, OutlinedBox>;
+
+pub fn create_layout_outline_map() -> LayoutOutlineMap {
+    LayoutOutlineMap::new()
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LayoutDumpRequest {
+    pub requested: bool
+}
+
+pub fn create_layout_dump_request() -> LayoutDumpRequest {
+    LayoutDumpRequest::default()
+}
+
+pub fn outline_colour(layout_type: Option<LayoutType>) -> StrokeColour {
+    match layout_type {
+        Some(LayoutType::Canvas) => StrokeColour::from((0, 170, 255, 255)),
+        Some(LayoutType::Horizontal) => StrokeColour::from((0, 200, 83, 255)),
+        Some(LayoutType::Vertical) => StrokeColour::from((255, 171, 0, 255)),
+        Some(LayoutType::Grid) => StrokeColour::from((170, 0, 255, 255)),
+        Some(LayoutType::Wrap) => StrokeColour::from((0, 188, 212, 255)),
+        Some(LayoutType::Dock) => StrokeColour::from((255, 87, 34, 255)),
+        Some(LayoutType::Layer) => StrokeColour::from((121, 85, 72, 255)),
+        Some(LayoutType::Custom(_)) => StrokeColour::from((233, 30, 99, 255)),
+        None => StrokeColour::from((158, 158, 158, 255))
+    }
+}
+
+pub fn overflow_outline_colour() -> StrokeColour {
+    StrokeColour::from((255, 0, 0, 255))
+}
+
+pub fn dump_layout(world: &World, relationship_map: &RelationshipMap) -> String {
+    let mut dump = String::new();
+    for root in <Entity>::query().filter(component::<Root>()).iter(world) {
+        dump_entity(world, relationship_map, root, 0, &mut dump);
+    }
+    dump
+}
+
+fn dump_entity(world: &World, relationship_map: &RelationshipMap, entity: &Entity, depth: usize, dump: &mut String) {
+    if let Ok(entry) = world.entry_ref(*entity) {
+        let layout = match entry.get_component::<LayoutContent>() {
+            Ok(layout) => format!("{:?}", layout.layout_type),
+            Err(_) => "Renderable".to_string()
+        };
+
+        write!(dump, "{:indent$}{:?} {}", "", entity, layout, indent = depth * 2).unwrap();
+
+        if let Ok(constraints) = entry.get_component::<CurrentLayoutConstraints>() {
+            write!(dump, " [{}, {}, {}, {}]", constraints.left, constraints.top, constraints.width, constraints.height).unwrap();
+        }
+
+        if let Ok(diagnostics) = entry.get_component::<LayoutDiagnostics>() {
+            if diagnostics.has_overflowed() {
+                write!(dump, " overflow [{}, {}]", diagnostics.overflow_width, diagnostics.overflow_height).unwrap();
+            }
+        }
+
+        if let Ok(visibility) = entry.get_component::<Visibility>() {
+            write!(dump, " {:?}", visibility).unwrap();
+        }
+
+        writeln!(dump).unwrap();
+    }
+
+    for child in relationship_map.get_children(entity) {
+        dump_entity(world, relationship_map, &child, depth + 1, dump);
+    }
+}
@@ -0,0 +1,94 @@
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+use crate::store::*;
+
+fn get_dock(store: &LayoutNodeStore, entity: &Entity) -> Dock {
+    store.get(entity).map(|node| node.dock).unwrap_or_default()
+}
+
+fn dock_axis(dock: Dock) -> Option<LayoutAxis> {
+    match dock {
+        Dock::Left | Dock::Right => Some(LayoutAxis::Horizontal),
+        Dock::Top | Dock::Bottom => Some(LayoutAxis::Vertical),
+        Dock::Fill => None
+    }
+}
+
+fn dock_child(remaining: &mut LayoutConstraints, dock: Dock, size: f32) -> LayoutConstraints {
+    let mut docked = *remaining;
+    match dock {
+        Dock::Left => {
+            docked.width = size.min(remaining.width);
+            remaining.left += docked.width;
+            remaining.width -= docked.width;
+        },
+        Dock::Right => {
+            docked.width = size.min(remaining.width);
+            remaining.width -= docked.width;
+            docked.left = remaining.left + remaining.width;
+        },
+        Dock::Top => {
+            docked.height = size.min(remaining.height);
+            remaining.top += docked.height;
+            remaining.height -= docked.height;
+        },
+        Dock::Bottom => {
+            docked.height = size.min(remaining.height);
+            remaining.height -= docked.height;
+            docked.top = remaining.top + remaining.height;
+        },
+        Dock::Fill => {}
+    }
+    docked
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct DockLayout {
+}
+
+impl LayoutAlgorithm for DockLayout {
+    fn measure(&self, maps: &MeasurementMaps, _: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        let (docked, others): (Vec<(Entity, f32)>, Vec<(Entity, f32)>) = child_sizes
+            .iter()
+            .partition(|(child, _)| dock_axis(get_dock(maps.store, child)) == Some(axis));
+
+        sum_of_sizes(&docked) + max_of_sizes(&others)
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let mut remaining = *constraints;
+        let mut overflow = LayoutDiagnostics::default();
+        let mut children = vec!();
+        let mut filling = vec!();
+
+        for child in maps.children(entity) {
+            let dock = get_dock(maps.store, &child);
+            match dock_axis(dock) {
+                Some(axis) => {
+                    let size = maps.minimum_size(&child, axis);
+                    let docked = dock_child(&mut remaining, dock, size);
+                    match axis {
+                        LayoutAxis::Horizontal => overflow.overflow_width += size - docked.width,
+                        LayoutAxis::Vertical => overflow.overflow_height += size - docked.height
+                    }
+                    children.push((child, docked));
+                },
+                None => filling.push(child)
+            }
+        }
+
+        if let Some(last_filling) = filling.pop() {
+            children.push((last_filling, remaining));
+        }
+
+        for child in filling {
+            children.push((child, LayoutConstraints { width: 0.0, height: 0.0, ..remaining }));
+        }
+
+        Arrangement::new(children).with_diagnostics(overflow)
+    }
+}
@@ -6,14 +6,18 @@ pub fn create_layout_event_reader_registry(event_channel: &mut EventChannel::<Sy
     LayoutEventReaderRegistry::register(event_channel)
 }
 pub struct LayoutEventReaderRegistry{
-    pub (crate) resize_screen: ReaderId<SystemEvent>
+    pub (crate) resize_screen: ReaderId<SystemEvent>,
+    pub (crate) text_input: ReaderId<SystemEvent>,
+    pub (crate) text_selection: ReaderId<SystemEvent>
 }
 
 impl LayoutEventReaderRegistry {
     fn register(event_channel: &mut EventChannel::<SystemEvent>) -> Self {
         info!("registering layout event readers");
         Self {
-            resize_screen: event_channel.register_reader() 
+            resize_screen: event_channel.register_reader(),
+            text_input: event_channel.register_reader(),
+            text_selection: event_channel.register_reader()
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+use crate::store::*;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GridDefinition {
+    pub rows: Vec<GridTrack>,
+    pub columns: Vec<GridTrack>,
+    pub row_gap: f32,
+    pub column_gap: f32
+}
+
+impl GridDefinition {
+    fn tracks(&self, axis: LayoutAxis) -> &[GridTrack] {
+        match axis {
+            LayoutAxis::Horizontal => &self.columns,
+            LayoutAxis::Vertical => &self.rows
+        }
+    }
+
+    fn gap(&self, axis: LayoutAxis) -> f32 {
+        match axis {
+            LayoutAxis::Horizontal => self.column_gap,
+            LayoutAxis::Vertical => self.row_gap
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridPlacement {
+    pub row: u16,
+    pub column: u16,
+    pub row_span: u16,
+    pub column_span: u16
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        Self {
+            row: 0,
+            column: 0,
+            row_span: 1,
+            column_span: 1
+        }
+    }
+}
+
+impl GridPlacement {
+    fn start(&self, axis: LayoutAxis) -> usize {
+        match axis {
+            LayoutAxis::Horizontal => self.column as usize,
+            LayoutAxis::Vertical => self.row as usize
+        }
+    }
+
+    fn span(&self, axis: LayoutAxis) -> usize {
+        let span = match axis {
+            LayoutAxis::Horizontal => self.column_span,
+            LayoutAxis::Vertical => self.row_span
+        };
+        span.max(1) as usize
+    }
+
+    fn end(&self, axis: LayoutAxis) -> usize {
+        self.start(axis) + self.span(axis)
+    }
+}
+
+fn get_definition(store: &LayoutNodeStore, entity: &Entity) -> GridDefinition {
+    store.get(entity).map(|node| node.grid_definition.clone()).unwrap_or_default()
+}
+
+fn get_placement(store: &LayoutNodeStore, entity: &Entity) -> GridPlacement {
+    store.get(entity).map(|node| node.grid_placement).unwrap_or_default()
+}
+
+fn gaps_size(gap: f32, track_count: usize) -> f32 {
+    gap * track_count.saturating_sub(1) as f32
+}
+
+fn total_size(sizes: &[f32], gap: f32) -> f32 {
+    sizes.iter().sum::<f32>() + gaps_size(gap, sizes.len())
+}
+
+fn resolve_tracks(
+    definition: &GridDefinition,
+    axis: LayoutAxis,
+    child_sizes: &[(GridPlacement, f32)],
+    available: Option<f32>) -> Vec<f32> {
+        let mut tracks = definition.tracks(axis).to_vec();
+        let track_count = child_sizes
+            .iter()
+            .map(|(placement, _)| placement.end(axis))
+            .fold(tracks.len(), usize::max);
+        tracks.resize(track_count, GridTrack::Auto);
+
+        let mut sizes: Vec<f32> = tracks
+            .iter()
+            .map(|track| match track {
+                GridTrack::Fixed(size) => *size as f32,
+                _ => 0.0
+            })
+            .collect();
+
+        for (placement, size) in child_sizes.iter().filter(|(placement, _)| placement.span(axis) == 1) {
+            let index = placement.start(axis);
+            match tracks[index] {
+                GridTrack::Fixed(_) => {},
+                GridTrack::Fraction(_) if available.is_some() => {},
+                _ => sizes[index] = sizes[index].max(*size)
+            }
+        }
+
+        if let Some(available) = available {
+            let remaining = (available - total_size(&sizes, definition.gap(axis))).max(0.0);
+            let fractions: u32 = tracks
+                .iter()
+                .map(|track| match track {
+                    GridTrack::Fraction(fraction) => *fraction as u32,
+                    _ => 0
+                })
+                .sum();
+
+            if fractions > 0 {
+                for (index, track) in tracks.iter().enumerate() {
+                    if let GridTrack::Fraction(fraction) = track {
+                        sizes[index] = remaining * *fraction as f32 / fractions as f32;
+                    }
+                }
+            } else {
+                let auto_count = tracks.iter().filter(|track| **track == GridTrack::Auto).count();
+                for (index, track) in tracks.iter().enumerate() {
+                    if *track == GridTrack::Auto {
+                        sizes[index] += remaining / auto_count as f32;
+                    }
+                }
+            }
+        }
+
+        sizes
+}
+
+fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    let mut offset = 0.0;
+    sizes
+        .iter()
+        .map(|size| {
+            let track_offset = offset;
+            offset += size + gap;
+            track_offset
+        })
+        .collect()
+}
+
+fn cell_span(offsets: &[f32], sizes: &[f32], start: usize, end: usize) -> (f32, f32) {
+    (offsets[start], offsets[end - 1] + sizes[end - 1] - offsets[start])
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct GridLayout {
+}
+
+impl LayoutAlgorithm for GridLayout {
+    fn measure(&self, maps: &MeasurementMaps, entity: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        let definition = get_definition(maps.store, entity);
+        let child_sizes: Vec<(GridPlacement, f32)> = child_sizes
+            .iter()
+            .map(|(child, size)| (get_placement(maps.store, child), *size))
+            .collect();
+
+        total_size(&resolve_tracks(&definition, axis, &child_sizes, None), definition.gap(axis))
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let definition = get_definition(maps.store, entity);
+        let children: Vec<(Entity, GridPlacement)> = maps
+            .children(entity)
+            .map(|child| (child, get_placement(maps.store, &child)))
+            .collect();
+
+        let child_widths: Vec<(GridPlacement, f32)> = children
+            .iter()
+            .map(|(child, placement)| (*placement, maps.minimum_size(child, LayoutAxis::Horizontal)))
+            .collect();
+
+        let child_heights: Vec<(GridPlacement, f32)> = children
+            .iter()
+            .map(|(child, placement)| (*placement, maps.minimum_size(child, LayoutAxis::Vertical)))
+            .collect();
+
+        let column_sizes = resolve_tracks(&definition, LayoutAxis::Horizontal, &child_widths, Some(constraints.width));
+        let row_sizes = resolve_tracks(&definition, LayoutAxis::Vertical, &child_heights, Some(constraints.height));
+        let column_offsets = track_offsets(&column_sizes, definition.column_gap);
+        let row_offsets = track_offsets(&row_sizes, definition.row_gap);
+
+        Arrangement::new(children
+            .into_iter()
+            .map(|(child, placement)| {
+                let (left, width) = cell_span(
+                    &column_offsets,
+                    &column_sizes,
+                    placement.start(LayoutAxis::Horizontal),
+                    placement.end(LayoutAxis::Horizontal));
+                let (top, height) = cell_span(
+                    &row_offsets,
+                    &row_sizes,
+                    placement.start(LayoutAxis::Vertical),
+                    placement.end(LayoutAxis::Vertical));
+                (child, LayoutConstraints { left: constraints.left + left, top: constraints.top + top, width, height })
+            })
+            .collect())
+            .with_diagnostics(LayoutDiagnostics {
+                overflow_width: (total_size(&column_sizes, definition.column_gap) - constraints.width).max(0.0),
+                overflow_height: (total_size(&row_sizes, definition.row_gap) - constraints.height).max(0.0)
+            })
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use shrev::*;
 use legion::*;
 use legion::systems::*;
@@ -17,32 +18,41 @@ impl ApplicationBundleBuilder for LayoutBundleBuilder {
         "standard layout".to_string()
     }
     
-    fn setup_build_systems(&self, _: &mut Builder) {
+    fn setup_build_systems(&self, builder: &mut Builder) {
+        builder
+            .add_system(initialise_text_edit_state_system())
+            .add_system(initialise_text_selection_system())
+            .add_system(remove_text_selection_system())
+            .flush()
+            .add_system(edit_text_inputs_system())
+            .add_system(select_text_system());
     }
 
     fn setup_layout_systems(&self, builder: &mut Builder) {
         builder
             .add_system(resize_screen_system())
-            .add_system(resize_after_rebuild_system())
             .flush()
-            .add_system(remove_from_left_offset_map_system())
-            .add_system(build_left_offset_map_system())
-            .add_system(remove_from_top_offset_map_system())
-            .add_system(build_top_offset_map_system())
-            .add_system(remove_from_minimum_width_map_system())
-            .add_system(remove_from_width_map_system())
-            .add_system(build_width_map_system())
-            .add_system(remove_from_minimum_height_map_system())
-            .add_system(remove_from_height_map_system())
-            .add_system(build_height_map_system())
-            .add_system(build_width_and_height_maps_from_radius_system())
-            .add_system(remove_from_layout_type_map_system())
-            .add_system(build_layout_type_map_system())
+            .add_system(build_layout_node_positions_system())
+            .add_system(build_layout_node_sizes_system())
+            .add_system(build_layout_node_content_system())
+            .add_system(build_layout_node_grid_definition_system())
+            .add_system(build_layout_node_placement_system())
+            .add_system(build_layout_node_text_system())
+            .add_system(build_layout_node_multi_line_wrap_system())
+            .add_system(build_layout_node_text_style_system())
+            .add_system(build_layout_node_text_decoration_system())
+            .add_system(remove_from_layout_node_store_system())
+            .add_system(mark_layout_dirty_system())
+            .add_system(mark_removed_parent_layout_dirty_system())
+            .add_system(remove_from_layout_box_map_system())
             .flush()
-            .add_system(measure_fixed_width_constraints_system())
-            .add_system(measure_fixed_height_constraints_system())
+            .add_system(measure_dirty_text_system())
+            .add_system(measure_dirty_layout_system())
             .flush()
-            .add_system(resize_system());
+            .add_system(resize_system())
+            .add_system(relayout_dirty_system())
+            .flush()
+            .add_system(layout_text_lines_system());
     }
 
     fn setup_rendering_systems(&self, _: &mut Builder) {
@@ -51,7 +61,8 @@ impl ApplicationBundleBuilder for LayoutBundleBuilder {
     fn setup_cleanup_systems(&self, builder: &mut Builder) { 
         builder
             .add_thread_local(remove_layout_change_system())
-            .add_thread_local(remove_resized_system());
+            .add_thread_local(remove_resized_system())
+            .add_thread_local(clear_layout_dirty_set_system());
 
     }
 
@@ -60,18 +71,135 @@ impl ApplicationBundleBuilder for LayoutBundleBuilder {
     
     fn setup_resources(&self, resources: &mut Resources, event_channel: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
         resources.insert(create_layout_event_reader_registry(event_channel));
-        resources.insert(create_text_colour_map());
-        resources.insert(create_layout_type_map());
-        resources.insert(create_left_offset_map());
-        resources.insert(create_top_offset_map());
-        resources.insert(create_width_map());
-        resources.insert(create_height_map());
-        resources.insert(create_minimum_width_map());
-        resources.insert(create_minimum_height_map());
+        register_fonts(resources, |_| Ok(()))?;
+        register_clipboard(resources);
+        resources.insert(create_layout_node_store());
+        resources.insert(create_layout_dirty_set());
+        resources.insert(create_layout_box_map());
+        register_layout_algorithms(resources, |_| {});
         
         Ok(())
     }    
     
     fn register_components_for_world_serializiation(&self, _: &mut zodiac::WorldSerializer) {
     }
+}
+
+pub fn layout_debugging() -> LayoutDebuggingBuilder {
+    LayoutDebuggingBuilder::default()
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LayoutDebuggingBuilder {
+    overlay: bool,
+    dump: bool
+}
+
+impl LayoutDebuggingBuilder {
+    pub fn with_overlay(mut self) -> Self {
+        self.overlay = true;
+        self
+    }
+
+    pub fn with_dump(mut self) -> Self {
+        self.dump = true;
+        self
+    }
+}
+
+impl ApplicationBundleBuilder for LayoutDebuggingBuilder {
+    fn description(&self) -> String {
+        "layout debugging".to_string()
+    }
+    
+    fn setup_build_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_layout_systems(&self, builder: &mut Builder) {
+        if self.overlay {
+            builder
+                .add_system(remove_layout_outline_system())
+                .add_system(outline_layout_system());
+        }
+        if self.dump {
+            builder.add_system(request_layout_dump_system());
+        }
+    }
+
+    fn setup_rendering_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_cleanup_systems(&self, _: &mut Builder) { 
+    }
+
+    fn setup_final_functions(&self, builder: &mut Builder) {
+        if self.dump {
+            builder.add_thread_local_fn(log_layout_dump);
+        }
+    }
+    
+    fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
+        resources.insert(create_layout_outline_map());
+        resources.insert(create_layout_dump_request());
+        Ok(())
+    }    
+    
+    fn register_components_for_world_serializiation(&self, _: &mut zodiac::WorldSerializer) {
+    }
+}
+
+pub fn custom_layout<T: LayoutAlgorithm + 'static>(layout_type: LayoutType, algorithm: T) -> CustomLayoutBuilder {
+    CustomLayoutBuilder::new(layout_type, Arc::new(algorithm))
+}
+
+#[derive(Clone)]
+pub struct CustomLayoutBuilder {
+    layout_type: LayoutType,
+    algorithm: Arc<dyn LayoutAlgorithm>
+}
+
+impl CustomLayoutBuilder {
+    fn new(layout_type: LayoutType, algorithm: Arc<dyn LayoutAlgorithm>) -> Self {
+        Self {
+            layout_type,
+            algorithm
+        }
+    }
+}
+
+impl ApplicationBundleBuilder for CustomLayoutBuilder {
+    fn description(&self) -> String {
+        format!("custom layout {:?}", self.layout_type)
+    }
+    
+    fn setup_build_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_layout_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_rendering_systems(&self, _: &mut Builder) {
+    }
+
+    fn setup_cleanup_systems(&self, _: &mut Builder) { 
+    }
+
+    fn setup_final_functions(&self, _: &mut Builder) {
+    }
+    
+    fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
+        register_layout_algorithms(resources, |registry| registry.register_shared(self.layout_type, self.algorithm.clone()));
+        Ok(())
+    }    
+    
+    fn register_components_for_world_serializiation(&self, _: &mut zodiac::WorldSerializer) {
+    }
+}
+
+fn register_layout_algorithms(resources: &mut Resources, register: impl FnOnce(&mut LayoutAlgorithmRegistry)) {
+    if !resources.contains::<LayoutAlgorithmRegistry>() {
+        resources.insert(create_layout_algorithm_registry());
+    }
+    let mut registry = resources.get_mut::<LayoutAlgorithmRegistry>().unwrap();
+    register(&mut *registry);
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::collections::{ HashMap, HashSet };
+use legion::*;
+use zodiac::*;
+
+#[derive(Default, Debug)]
+pub struct LayoutDirtySet {
+    dirty: HashSet<Entity>,
+    relayout_roots: HashSet<Entity>
+}
+
+pub fn create_layout_dirty_set() -> LayoutDirtySet {
+    LayoutDirtySet::default()
+}
+
+impl LayoutDirtySet {
+    pub fn mark(&mut self, entity: Entity) {
+        self.dirty.insert(entity);
+    }
+
+    pub fn is_dirty(&self, entity: &Entity) -> bool {
+        self.dirty.contains(entity)
+    }
+
+    pub fn dirty(&self) -> impl Iterator<Item = &Entity> {
+        self.dirty.iter()
+    }
+
+    pub fn topmost(&self, relationship_map: &RelationshipMap) -> Vec<Entity> {
+        topmost_of(&self.dirty, relationship_map)
+    }
+
+    pub fn contains_dirty(&self, relationship_map: &RelationshipMap, entity: &Entity) -> bool {
+        self.dirty
+            .iter()
+            .any(|dirty| dirty == entity || has_ancestor(relationship_map, dirty, entity))
+    }
+
+    pub fn add_relayout_root(&mut self, entity: Entity) {
+        self.relayout_roots.insert(entity);
+    }
+
+    pub fn relayout_roots(&self, relationship_map: &RelationshipMap) -> Vec<Entity> {
+        topmost_of(&self.relayout_roots, relationship_map)
+    }
+
+    pub fn clear(&mut self) {
+        self.dirty.clear();
+        self.relayout_roots.clear();
+    }
+}
+
+fn topmost_of(entities: &HashSet<Entity>, relationship_map: &RelationshipMap) -> Vec<Entity> {
+    entities
+        .iter()
+        .filter(|entity| !has_ancestor_in(entities, relationship_map, entity))
+        .copied()
+        .collect()
+}
+
+fn has_ancestor(relationship_map: &RelationshipMap, entity: &Entity, ancestor: &Entity) -> bool {
+    let mut current = relationship_map.get_parent(entity);
+    while let Some(parent) = current {
+        if parent == *ancestor {
+            return true;
+        }
+        current = relationship_map.get_parent(&parent);
+    }
+    false
+}
+
+fn has_ancestor_in(entities: &HashSet<Entity>, relationship_map: &RelationshipMap, entity: &Entity) -> bool {
+    let mut current = relationship_map.get_parent(entity);
+    while let Some(parent) = current {
+        if entities.contains(&parent) {
+            return true;
+        }
+        current = relationship_map.get_parent(&parent);
+    }
+    false
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct LayoutBox {
+    pub change: LayoutChange,
+    pub direction: Direction,
+    pub visible: bool
+}
+
+pub type LayoutBoxMap = HashMap<Entity, LayoutBox>;
+
+pub fn create_layout_box_map() -> LayoutBoxMap {
+    LayoutBoxMap::new()
+}
@@ -0,0 +1,74 @@
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+use crate::stacks::*;
+use crate::store::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AxisAlignment {
+    Start,
+    Centre,
+    End,
+    Stretch
+}
+
+fn axis_alignments(alignment: Alignment) -> (AxisAlignment, AxisAlignment) {
+    match alignment {
+        Alignment::TopLeft => (AxisAlignment::Start, AxisAlignment::Start),
+        Alignment::TopCentre => (AxisAlignment::Centre, AxisAlignment::Start),
+        Alignment::TopRight => (AxisAlignment::End, AxisAlignment::Start),
+        Alignment::CentreLeft => (AxisAlignment::Start, AxisAlignment::Centre),
+        Alignment::Centre => (AxisAlignment::Centre, AxisAlignment::Centre),
+        Alignment::CentreRight => (AxisAlignment::End, AxisAlignment::Centre),
+        Alignment::BottomLeft => (AxisAlignment::Start, AxisAlignment::End),
+        Alignment::BottomCentre => (AxisAlignment::Centre, AxisAlignment::End),
+        Alignment::BottomRight => (AxisAlignment::End, AxisAlignment::End),
+        Alignment::Stretch => (AxisAlignment::Stretch, AxisAlignment::Stretch)
+    }
+}
+
+fn align(alignment: AxisAlignment, start: f32, available: f32, size: f32) -> (f32, f32) {
+    if alignment == AxisAlignment::Stretch || size <= 0.0 {
+        return (start, available);
+    }
+    let size = size.min(available);
+    match alignment {
+        AxisAlignment::Centre => (start + (available - size) / 2.0, size),
+        AxisAlignment::End => (start + available - size, size),
+        _ => (start, size)
+    }
+}
+
+fn get_alignment(store: &LayoutNodeStore, entity: &Entity) -> Alignment {
+    store.get(entity).map(|node| node.alignment).unwrap_or_default()
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct LayerLayout {
+}
+
+impl LayoutAlgorithm for LayerLayout {
+    fn measure(&self, _: &MeasurementMaps, _: &Entity, _: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        max_of_sizes(child_sizes)
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        Arrangement::new(maps
+            .children(entity)
+            .map(|child| {
+                let alignment = get_alignment(maps.store, &child);
+                let (horizontal, vertical) = axis_alignments(alignment);
+                let (left, width) = align(horizontal, constraints.left, constraints.width, maps.minimum_size(&child, LayoutAxis::Horizontal));
+                let (top, height) = align(vertical, constraints.top, constraints.height, maps.minimum_size(&child, LayoutAxis::Vertical));
+                (child, LayoutConstraints { left, top, width, height })
+            })
+            .collect())
+            .with_diagnostics(LayoutDiagnostics {
+                overflow_width: measure_width_overflow(maps, entity, constraints),
+                overflow_height: measure_height_overflow(maps, entity, constraints)
+            })
+    }
+}
@@ -1,20 +1,46 @@
 pub mod text;
+pub mod shaping;
 pub mod measurement;
 pub mod constraints;
 pub mod positioning;
 pub mod resizing;
+pub mod algorithms;
+pub mod canvas;
+pub mod stacks;
+pub mod grid;
+pub mod wrapping;
+pub mod docking;
+pub mod layering;
+pub mod invalidation;
+pub mod store;
+pub mod debugging;
 pub mod systems;
 pub mod events;
 pub mod initialisation;
 
 pub use text::*;
+pub use shaping::*;
 pub use measurement::*;
 pub use constraints::*;
 pub use positioning::*;
 pub use resizing::*;
+pub use algorithms::*;
+pub use canvas::*;
+pub use stacks::*;
+pub use grid::*;
+pub use wrapping::*;
+pub use docking::*;
+pub use layering::*;
+pub use invalidation::*;
+pub use store::*;
+pub use debugging::*;
 pub use events::*;
 pub use initialisation::*;
+pub use systems::store::*;
 pub use systems::measurement::*;
-pub use systems::positioning::*;
 pub use systems::resizing::*;
+pub use systems::invalidation::*;
+pub use systems::debugging::*;
+pub use systems::text::*;
+pub use systems::editing::*;
 
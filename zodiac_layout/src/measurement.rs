@@ -1,71 +1,147 @@
-use std::collections::{ HashMap };
 use legion::*;
 use zodiac::*;
-
-pub type WidthMap = HashMap<Entity, Width>;
-
-pub fn create_width_map() -> WidthMap {
-    WidthMap::new()
+use crate::algorithms::*;
+use crate::invalidation::*;
+use crate::store::*;
+use crate::text::*;
+
+pub struct MeasurementMaps<'a> {
+    pub relationship_map: &'a RelationshipMap,
+    pub store: &'a LayoutNodeStore,
+    pub algorithms: &'a LayoutAlgorithmRegistry
 }
 
-pub type HeightMap = HashMap<Entity, Height>;
-
-pub fn create_height_map() -> HeightMap {
-    HeightMap::new()
-}
+impl<'a> MeasurementMaps<'a> {
+    fn measure_content(&self, node: &LayoutNode, entity: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        match node.layout_type.as_ref().and_then(|layout_type| self.algorithms.get(layout_type)) {
+            Some(algorithm) => algorithm.measure(self, entity, axis, child_sizes),
+            None => max_of_sizes(child_sizes)
+        }
+    }
+
+    fn measure(&self, slot: LayoutSlot, entity: &Entity, axis: LayoutAxis) -> f32 {
+        let node = self.store.node(slot);
+        let sizes = self.store.sizes(slot);
+
+        let child_sizes: Vec<(Entity, f32)> = self.relationship_map
+            .get_children(entity)
+            .filter_map(|child| self.store.slot(&child).map(|child_slot| (child, child_slot)))
+            .filter(|(_, child_slot)| self.store.visibility(*child_slot) != Visibility::Collapsed)
+            .map(|(child, child_slot)| (child, self.store.minimum_size(child_slot, axis)))
+            .collect();
+
+        let mut minimum_size = if sizes.wraps_content() {
+            self.measure_content(node, entity, axis, &child_sizes)
+        } else {
+            sum_of_sizes(&child_sizes)
+        };
+
+        if let Some(size) = node.intrinsic_size(axis) {
+            minimum_size = size;
+        }
 
-pub type MinimumWidthMap = HashMap<Entity, MinimumWidth>;
+        if let Some(size) = sizes.fixed_size(axis) {
+            minimum_size = size;
+        } else if let (Some(cross_size), Some(aspect_ratio)) = (sizes.fixed_size(axis.cross()), node.aspect_ratio) {
+            minimum_size = match axis {
+                LayoutAxis::Horizontal => aspect_ratio.width_for(cross_size),
+                LayoutAxis::Vertical => aspect_ratio.height_for(cross_size)
+            };
+        }
 
-pub fn create_minimum_width_map() -> MinimumWidthMap {
-    MinimumWidthMap::new()
+        minimum_size
+    }
 }
 
-pub type MinimumHeightMap = HashMap<Entity, MinimumHeight>;
-
-pub fn create_minimum_height_map() -> MinimumHeightMap {
-    MinimumHeightMap::new()
-}              
-
-pub fn measure_fixed_widths(
+pub fn measure_subtree(
     relationship_map: &RelationshipMap,
-    width_map: &WidthMap,
-    minimum_width_map: &mut MinimumWidthMap,
-    entity: &Entity) -> u16 {
-        let mut minimum_width = 0;
-
+    algorithms: &LayoutAlgorithmRegistry,
+    store: &mut LayoutNodeStore,
+    entity: &Entity,
+    axis: LayoutAxis) -> f32 {
         for child in relationship_map.get_children(entity) {
-            minimum_width += measure_fixed_widths(relationship_map, width_map, minimum_width_map, &child);
-        }
-
-        if let Some(width) = width_map.get(entity) {
-            minimum_width = width.width;
+            measure_subtree(relationship_map, algorithms, store, &child, axis);
         }
+        measure(relationship_map, algorithms, store, entity, axis)
+}
 
-        if minimum_width > 0 {
-            minimum_width_map.insert(*entity, MinimumWidth { width: minimum_width });
-        }
+pub fn measure(
+    relationship_map: &RelationshipMap,
+    algorithms: &LayoutAlgorithmRegistry,
+    store: &mut LayoutNodeStore,
+    entity: &Entity,
+    axis: LayoutAxis) -> f32 {
+        let slot = match store.slot(entity) {
+            Some(slot) => slot,
+            None => return 0.0
+        };
+        let minimum_size = MeasurementMaps { relationship_map, store: &*store, algorithms }.measure(slot, entity, axis);
+        store.sizes_mut(slot).set_minimum_size(axis, minimum_size);
+        minimum_size
+}
 
-        minimum_width
+fn minimum_sizes(store: &LayoutNodeStore, entity: &Entity) -> (Option<f32>, Option<f32>) {
+    match store.slot(entity).map(|slot| store.sizes(slot)) {
+        Some(sizes) => (sizes.minimum_width, sizes.minimum_height),
+        None => (None, None)
+    }
 }
 
-pub fn measure_fixed_heights(
+pub fn measure_dirty_subtrees(
     relationship_map: &RelationshipMap,
-    height_map: &HeightMap,
-    minimum_height_map: &mut MinimumHeightMap,
-    entity: &Entity) -> u16 {
-        let mut minimum_height = 0;
-
-        for child in relationship_map.get_children(entity) {
-            minimum_height += measure_fixed_heights(relationship_map, height_map, minimum_height_map, &child);
+    algorithms: &LayoutAlgorithmRegistry,
+    store: &mut LayoutNodeStore,
+    dirty_set: &mut LayoutDirtySet) {
+        for entity in dirty_set.topmost(relationship_map) {
+            measure_subtree(relationship_map, algorithms, store, &entity, LayoutAxis::Horizontal);
+            measure_subtree(relationship_map, algorithms, store, &entity, LayoutAxis::Vertical);
+
+            let mut relayout_root = entity;
+            let mut current = relationship_map.get_parent(&entity);
+
+            while let Some(parent) = current {
+                relayout_root = parent;
+                let previous_sizes = minimum_sizes(store, &parent);
+                measure(relationship_map, algorithms, store, &parent, LayoutAxis::Horizontal);
+                measure(relationship_map, algorithms, store, &parent, LayoutAxis::Vertical);
+                if minimum_sizes(store, &parent) == previous_sizes {
+                    break;
+                }
+                current = relationship_map.get_parent(&parent);
+            }
+
+            dirty_set.add_relayout_root(relayout_root);
         }
+}
 
-        if let Some(height) = height_map.get(entity) {
-            minimum_height = height.height;
+pub fn measure_dirty_paragraphs(
+    relationship_map: &RelationshipMap,
+    fonts: &FontRegistry,
+    store: &mut LayoutNodeStore,
+    dirty_set: &mut LayoutDirtySet) {
+        let span_parents: Vec<Entity> = dirty_set
+            .dirty()
+            .filter(|entity| store.get(entity).map_or(false, |node| node.is_span()))
+            .filter_map(|entity| relationship_map.get_parent(entity))
+            .collect();
+
+        for parent in span_parents {
+            dirty_set.mark(parent);
         }
 
-        if minimum_height > 0 {
-            minimum_height_map.insert(*entity, MinimumHeight { height: minimum_height });
+        let paragraphs: Vec<Entity> = dirty_set
+            .dirty()
+            .filter(|entity| store.get(entity).map_or(false, |node| node.text_format.is_some()))
+            .copied()
+            .collect();
+
+        for entity in paragraphs {
+            if let Some(slot) = store.slot(&entity) {
+                let spans = store.paragraph_spans(relationship_map, &entity);
+                let available_width = store.sizes(slot).width.unwrap_or(f32::INFINITY);
+                let direction = store.inherited_direction(relationship_map, &entity);
+                let node = store.node_mut(slot);
+                node.text_size = node.text_format.map(|format| measure_text(fonts, &spans, &format, available_width, direction));
+            }
         }
-
-        minimum_height
 }
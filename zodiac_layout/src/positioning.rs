@@ -1,21 +1,31 @@
-use std::collections::{ HashMap };
-use legion::*;
-use zodiac::*;
-
-pub type LeftOffsetMap = HashMap<Entity, Left>;
-
-pub fn create_left_offset_map() -> LeftOffsetMap {
-    LeftOffsetMap::new()
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Anchors {
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+    pub center_x: Option<f32>,
+    pub center_y: Option<f32>
 }
 
-pub type TopOffsetMap = HashMap<Entity, Top>;
-
-pub fn create_top_offset_map() -> TopOffsetMap {
-    TopOffsetMap::new()
+pub fn anchor(
+    start: f32,
+    available: f32,
+    size: Option<f32>,
+    near: Option<f32>,
+    far: Option<f32>,
+    centre: Option<f32>) -> (f32, f32) {
+        match (near, far) {
+            (Some(near), Some(far)) => (start + near, (available - near - far).max(0.0)),
+            (Some(near), None) => (start + near, size.unwrap_or(available)),
+            (None, Some(far)) => {
+                let size = size.unwrap_or(available);
+                (start + available - far - size, size)
+            },
+            (None, None) => {
+                let size = size.unwrap_or(available);
+                match centre {
+                    Some(offset) => (start + (available - size) / 2.0 + offset, size),
+                    None => (start, size)
+                }
+            }
+        }
 }
-
-pub type LayoutTypeMap = HashMap<Entity, LayoutType>;
-
-pub fn create_layout_type_map() -> LayoutTypeMap {
-    LayoutTypeMap::new()
-}
\ No newline at end of file
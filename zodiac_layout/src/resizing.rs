@@ -1,125 +1,285 @@
 use legion::*;
-use log::{debug};
-use legion::world::*;
+use log::{debug, warn};
 use legion::systems::*;
 use zodiac::*;
 use crate::constraints::*;
 use crate::positioning::*;
-use crate::measurement::*;
+use crate::algorithms::*;
+use crate::invalidation::*;
+use crate::store::*;
 
 pub struct LayoutMaps<'a> {
     pub relationship_map: &'a RelationshipMap,
-    pub layout_map: &'a LayoutTypeMap,
-    pub left_map: &'a LeftOffsetMap,
-    pub top_map: &'a TopOffsetMap,
-    pub width_map: &'a WidthMap,
-    pub minimum_width_map: &'a MinimumWidthMap,
-    pub height_map: &'a HeightMap,
-    pub minimum_height_map: &'a MinimumHeightMap
+    pub store: &'a LayoutNodeStore,
+    pub algorithms: &'a LayoutAlgorithmRegistry,
+    pub dirty_set: &'a LayoutDirtySet
+}
+
+impl<'a> LayoutMaps<'a> {
+    pub fn minimum_size(&self, entity: &Entity, axis: LayoutAxis) -> f32 {
+        self.store.slot(entity).map_or(0.0, |slot| self.store.minimum_size(slot, axis))
+    }
+
+    pub fn children(&self, entity: &Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.child_slots(entity).map(|(child, _)| child)
+    }
+
+    pub fn child_slots(&self, entity: &Entity) -> impl Iterator<Item = (Entity, LayoutSlot)> + '_ {
+        self.relationship_map
+            .get_children(entity)
+            .filter_map(move |child| self.store.slot(&child).map(|slot| (child, slot)))
+            .filter(move |(_, slot)| self.store.visibility(*slot) != Visibility::Collapsed)
+    }
+
+    pub fn direction(&self, entity: &Entity) -> Direction {
+        self.store.inherited_direction(self.relationship_map, entity)
+    }
+
+    pub fn is_visible(&self, entity: &Entity) -> bool {
+        self.ancestor_slots(entity).all(|slot| self.store.visibility(slot) == Visibility::Visible)
+    }
+
+    pub fn is_collapsed(&self, entity: &Entity) -> bool {
+        self.ancestor_slots(entity).any(|slot| self.store.visibility(slot) == Visibility::Collapsed)
+    }
+
+    fn ancestor_slots(&self, entity: &Entity) -> impl Iterator<Item = LayoutSlot> + '_ {
+        self.ancestry(entity).filter_map(move |ancestor| self.store.slot(&ancestor))
+    }
+
+    fn ancestry(&self, entity: &Entity) -> impl Iterator<Item = Entity> + '_ {
+        let relationship_map = self.relationship_map;
+        std::iter::successors(Some(*entity), move |current| relationship_map.get_parent(current))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Inheritance {
+    direction: Direction,
+    visible: bool
+}
+
+impl Inheritance {
+    fn for_entity(maps: &LayoutMaps, entity: &Entity) -> Self {
+        Self {
+            direction: maps.direction(entity),
+            visible: maps.is_visible(entity)
+        }
+    }
+
+    fn inherit(&self, store: &LayoutNodeStore, slot: Option<LayoutSlot>) -> Self {
+        match slot {
+            Some(slot) => Self {
+                direction: store.direction(slot).unwrap_or(self.direction),
+                visible: self.visible && store.visibility(slot) == Visibility::Visible
+            },
+            None => *self
+        }
+    }
 }
 
 pub fn perform_resize(
     maps: &LayoutMaps,
-    world: &mut SubWorld,
     command_buffer: &mut CommandBuffer,
+    layout_boxes: &mut LayoutBoxMap,
     entity: &Entity, 
     constraints: &LayoutConstraints) {
         command_buffer.add_component(*entity, Width { width: constraints.width });
         command_buffer.add_component(*entity, Height { height: constraints.height });
         command_buffer.add_component(*entity, Resized::default());
         command_buffer.remove_component::<LayoutRequest>(*entity);
-        perform_layout(maps, world, command_buffer, entity, constraints);
+        perform_layout(maps, command_buffer, layout_boxes, entity, constraints);
 }
 
 pub fn perform_layout(
     maps: &LayoutMaps,
-    world: &mut SubWorld,
     command_buffer: &mut CommandBuffer,
+    layout_boxes: &mut LayoutBoxMap,
     entity: &Entity, 
     constraints: &LayoutConstraints) {
-        if let Some(layout_type) = maps.layout_map.get(entity) {
-            match layout_type {
-                LayoutType::Canvas => layout_canvas(maps, world, command_buffer, entity, constraints),
-                LayoutType::Horizontal => layout_horizontal(maps, world, command_buffer, entity, constraints),
-                LayoutType::Vertical => layout_vertical(maps, world, command_buffer, entity, constraints)
+        let inheritance = match maps.relationship_map.get_parent(entity) {
+            Some(parent) => Inheritance::for_entity(maps, &parent),
+            None => Inheritance { direction: Direction::default(), visible: true }
+        };
+        layout_entity(maps, command_buffer, layout_boxes, entity, constraints, inheritance);
+}
+
+fn layout_entity(
+    maps: &LayoutMaps,
+    command_buffer: &mut CommandBuffer,
+    layout_boxes: &mut LayoutBoxMap,
+    entity: &Entity, 
+    constraints: &LayoutConstraints,
+    inheritance: Inheritance) {
+        let slot = maps.store.slot(entity);
+        let node = slot.map(|slot| maps.store.node(slot));
+        let inheritance = inheritance.inherit(maps.store, slot);
+        let fitted = fit_to_content(maps.store, slot, constraints);
+        let constraints = &match slot.filter(|slot| maps.store.node(*slot).layout_type.is_some()) {
+            Some(slot) => anchor_container(maps.store.node(slot), maps.store.sizes(slot), &fitted, constraints, inheritance.direction),
+            None => fitted
+        };
+        if let Some(layout_type) = node.and_then(|node| node.layout_type) {
+            let layout_box = LayoutBox {
+                change: LayoutChange::from(constraints),
+                direction: inheritance.direction,
+                visible: inheritance.visible
+            };
+            let previous = layout_boxes.insert(*entity, layout_box);
+            if previous == Some(layout_box) && !maps.dirty_set.contains_dirty(maps.relationship_map, entity) {
+                debug!("Layout unchanged for {:?}", entity);
+                return;
+            }
+            if let Some(algorithm) = maps.algorithms.get(&layout_type) {
+                let arrangement = algorithm.arrange(maps, entity, constraints);
+                if let Some(diagnostics) = arrangement.diagnostics {
+                    record_diagnostics(command_buffer, entity, diagnostics);
+                }
+                for (child, new_constraints) in arrangement.children {
+                    let new_constraints = apply_direction(inheritance.direction, &new_constraints, constraints);
+                    layout_entity(maps, command_buffer, layout_boxes, &child, &new_constraints, inheritance);
+                }
+                for child in maps.relationship_map.get_children(entity) {
+                    if maps.store.slot(&child).map_or(false, |child_slot| maps.store.visibility(child_slot) == Visibility::Collapsed) {
+                        conceal_subtree(maps, command_buffer, layout_boxes, &child);
+                    }
+                }
+            } else {
+                warn!("No layout algorithm registered for {:?}", layout_type);
             }
         } else {
-            layout_renderable(maps, command_buffer, entity, constraints);
+            layout_renderable(maps, command_buffer, layout_boxes, entity, slot, constraints, inheritance);
         }
         command_buffer.add_component(*entity, CurrentLayoutConstraints::from(constraints));
 }
 
-pub fn layout_canvas(
+fn conceal_subtree(
     maps: &LayoutMaps,
-    world: &mut SubWorld,
     command_buffer: &mut CommandBuffer,
-    entity: &Entity, 
-    constraints: &LayoutConstraints) {
-        let mut new_constraints = *constraints;
-        if let Some(left) = maps.left_map.get(entity) {
-            new_constraints = new_constraints + *left;
-        }
-        if let Some(top) = maps.top_map.get(entity) {
-            new_constraints = new_constraints + *top;
+    layout_boxes: &mut LayoutBoxMap,
+    entity: &Entity) {
+        let previous = layout_boxes.get(entity).copied();
+        if previous.map_or(true, |previous| previous.visible) {
+            debug!("Concealing collapsed {:?}", entity);
+            command_buffer.add_component(*entity, Invisible::default());
+            layout_boxes.insert(*entity, LayoutBox { visible: false, ..previous.unwrap_or_default() });
         }
         for child in maps.relationship_map.get_children(entity) {
-            perform_layout(maps, world, command_buffer, &child, &new_constraints);
-        } 
+            conceal_subtree(maps, command_buffer, layout_boxes, &child);
+        }
 }
 
-fn layout_horizontal(
-    maps: &LayoutMaps,
-    world: &mut SubWorld,
-    command_buffer: &mut CommandBuffer,
-    entity: &Entity, 
-    constraints: &LayoutConstraints) {
-        let mut subdivider = constraints.into_width_subdivider(&maps.minimum_width_map);
+fn apply_direction(direction: Direction, constraints: &LayoutConstraints, container: &LayoutConstraints) -> LayoutConstraints {
+    match direction {
+        Direction::LeftToRight => *constraints,
+        Direction::RightToLeft => constraints.mirror_horizontally(container)
+    }
+}
 
-        for child in maps.relationship_map.get_children(entity) {
-            subdivider.subdivide_for_entity(&child);
+fn fit_to_content(store: &LayoutNodeStore, slot: Option<LayoutSlot>, constraints: &LayoutConstraints) -> LayoutConstraints {
+    let mut fitted = *constraints;
+    if let Some(sizes) = slot.map(|slot| store.sizes(slot)).filter(|sizes| sizes.wraps_content()) {
+        if let Some(minimum_width) = sizes.minimum_width {
+            fitted.width = fitted.width.min(minimum_width);
         }
-        
-        for (child, new_constraints) in subdivider.iter() {
-            perform_layout(maps, world, command_buffer, &child, &new_constraints);
+        if let Some(minimum_height) = sizes.minimum_height {
+            fitted.height = fitted.height.min(minimum_height);
         }
+    }
+    fitted
 }
 
-fn layout_vertical(
-    maps: &LayoutMaps,
-    world: &mut SubWorld,
-    command_buffer: &mut CommandBuffer,
-    entity: &Entity, 
-    constraints: &LayoutConstraints) {
-        let mut subdivider = constraints.into_height_subdivider(&maps.minimum_height_map);
+fn anchor_container(node: &LayoutNode, sizes: &LayoutSizes, fitted: &LayoutConstraints, constraints: &LayoutConstraints, direction: Direction) -> LayoutConstraints {
+    let anchors = node.anchors;
+    let anchored_horizontally = anchors.right.is_some() || anchors.center_x.is_some();
+    let anchored_vertically = anchors.bottom.is_some() || anchors.center_y.is_some();
+    if !anchored_horizontally && !anchored_vertically {
+        return *fitted;
+    }
 
-        for child in maps.relationship_map.get_children(entity) {
-            subdivider.subdivide_for_entity(&child);
-        }
-        
-        for (child, new_constraints) in subdivider.iter() {
-            perform_layout(maps, world, command_buffer, &child, &new_constraints);
-        }
+    let mut anchored = *fitted;
+    if anchored_horizontally {
+        let width = sizes.width.or_else(|| Some(fitted.width).filter(|_| sizes.wraps_content()));
+        let (left, width) = anchor(constraints.left, constraints.width, width, node.left, anchors.right, anchors.center_x);
+        anchored.left = left;
+        anchored.width = width;
+    }
+
+    if anchored_vertically {
+        let height = sizes.height.or_else(|| Some(fitted.height).filter(|_| sizes.wraps_content()));
+        let (top, height) = anchor(constraints.top, constraints.height, height, node.top, anchors.bottom, anchors.center_y);
+        anchored.top = top;
+        anchored.height = height;
+    }
+
+    apply_direction(direction, &anchored, constraints)
+}
+
+fn record_diagnostics(command_buffer: &mut CommandBuffer, entity: &Entity, diagnostics: LayoutDiagnostics) {
+    if diagnostics.has_overflowed() {
+        warn!("Layout overflow for {:?} {:?}", entity, diagnostics);
+    }
+    command_buffer.add_component(*entity, diagnostics);
 }
 
 fn layout_renderable(
     maps: &LayoutMaps,
     command_buffer: &mut CommandBuffer,
+    layout_boxes: &mut LayoutBoxMap,
     entity: &Entity, 
-    constraints: &LayoutConstraints) {
-        let mut layout_change = LayoutChange::from(constraints);
-        
-        if let Some(left) = maps.left_map.get(entity) {
-            layout_change = layout_change + *left;
-        }
-        if let Some(top) = maps.top_map.get(entity) {
-            layout_change = layout_change + *top;
-        }
-        if let Some(width) = maps.width_map.get(entity) {
-            layout_change.width = width.width;
+    slot: Option<LayoutSlot>,
+    constraints: &LayoutConstraints,
+    inheritance: Inheritance) {
+        let unstored = LayoutNode::default();
+        let node = slot.map_or(&unstored, |slot| maps.store.node(slot));
+        let sizes = slot.map(|slot| *maps.store.sizes(slot)).unwrap_or_default();
+        let mut width = sizes.width;
+        let mut height = sizes.height;
+
+        if let Some(aspect_ratio) = node.aspect_ratio {
+            match (width, height) {
+                (Some(fixed_width), None) => height = Some(aspect_ratio.height_for(fixed_width)),
+                (None, Some(fixed_height)) => width = Some(aspect_ratio.width_for(fixed_height)),
+                (None, None) => {
+                    let (fitted_width, fitted_height) = aspect_ratio.fit(constraints.width, constraints.height);
+                    width = Some(fitted_width);
+                    height = Some(fitted_height);
+                },
+                _ => {}
+            }
         }
-        if let Some(height) = maps.height_map.get(entity) {
-            layout_change.height = height.height;
+
+        let (left, width) = anchor(
+            constraints.left,
+            constraints.width,
+            width,
+            node.left,
+            node.anchors.right,
+            node.anchors.center_x);
+
+        let (top, height) = anchor(
+            constraints.top,
+            constraints.height,
+            height,
+            node.top,
+            node.anchors.bottom,
+            node.anchors.center_y);
+
+        let placed = apply_direction(inheritance.direction, &LayoutConstraints { left, top, width, height }, constraints);
+        let layout_box = LayoutBox {
+            change: LayoutChange::from(&placed),
+            direction: inheritance.direction,
+            visible: inheritance.visible
+        };
+        let previous = layout_boxes.insert(*entity, layout_box);
+        if previous != Some(layout_box) || maps.dirty_set.is_dirty(entity) {
+            debug!("Layout change for {:?} {:?}", entity, layout_box);
+            command_buffer.add_component(*entity, layout_box.change);
+            command_buffer.add_component(*entity, CurrentDirection { direction: layout_box.direction });
+            match (previous.map(|previous| previous.visible), layout_box.visible) {
+                (Some(true), true) | (Some(false), false) => {},
+                (_, true) => command_buffer.remove_component::<Invisible>(*entity),
+                (_, false) => command_buffer.add_component(*entity, Invisible::default())
+            }
         }
-        debug!("Layout change for {:?} {:?}", entity, layout_change);
-        command_buffer.add_component(*entity, layout_change);
-}
\ No newline at end of file
+}
@@ -0,0 +1,92 @@
+use rustybuzz::UnicodeBuffer;
+use unicode_bidi::{BidiInfo, Level};
+use zodiac::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ShapedGlyph {
+    pub id: u16,
+    pub cluster: usize,
+    pub left: f32,
+    pub top: f32,
+    pub advance: f32
+}
+
+pub fn shape_text(face: &FontFace, text: &str, direction: Direction, font_size: f32) -> Vec<ShapedGlyph> {
+    let scale = face.scale(font_size);
+    let shaper = match face.shaper() {
+        Some(shaper) => shaper,
+        None => return text
+            .char_indices()
+            .map(|(cluster, character)| ShapedGlyph {
+                id: face.glyph_id(character),
+                cluster,
+                advance: face.advance(character, font_size),
+                ..ShapedGlyph::default()
+            })
+            .collect()
+    };
+
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_direction(match direction {
+        Direction::LeftToRight => rustybuzz::Direction::LeftToRight,
+        Direction::RightToLeft => rustybuzz::Direction::RightToLeft
+    });
+    buffer.guess_segment_properties();
+
+    let shaped = rustybuzz::shape(shaper, &[], buffer);
+    shaped.glyph_infos()
+        .iter()
+        .zip(shaped.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            id: info.glyph_id as u16,
+            cluster: info.cluster as usize,
+            left: position.x_offset as f32 * scale,
+            top: -position.y_offset as f32 * scale,
+            advance: position.x_advance as f32 * scale
+        })
+        .collect()
+}
+
+pub fn bidi_levels(text: &str, direction: Direction) -> Vec<u8> {
+    let paragraph_level = match direction {
+        Direction::LeftToRight => Level::ltr(),
+        Direction::RightToLeft => Level::rtl()
+    };
+    let bidi_info = BidiInfo::new(text, Some(paragraph_level));
+    text.char_indices()
+        .map(|(index, _)| bidi_info.levels[index].number())
+        .collect()
+}
+
+pub fn level_direction(level: u8) -> Direction {
+    if level % 2 == 0 {
+        Direction::LeftToRight
+    } else {
+        Direction::RightToLeft
+    }
+}
+
+pub fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let highest = levels.iter().copied().max().unwrap_or_default();
+    let lowest = levels.iter().copied().min().unwrap_or_default();
+    let lowest_odd = lowest | 1;
+
+    for level in (lowest_odd..=highest).rev() {
+        let mut index = 0;
+        while index < order.len() {
+            if levels[order[index]] < level {
+                index += 1;
+                continue;
+            }
+            let start = index;
+            while index < order.len() && levels[order[index]] >= level {
+                index += 1;
+            }
+            order[start..index].reverse();
+        }
+    }
+
+    order
+}
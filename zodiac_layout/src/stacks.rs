@@ -0,0 +1,76 @@
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct HorizontalLayout {
+}
+
+impl LayoutAlgorithm for HorizontalLayout {
+    fn measure(&self, _: &MeasurementMaps, _: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        match axis {
+            LayoutAxis::Horizontal => sum_of_sizes(child_sizes),
+            LayoutAxis::Vertical => max_of_sizes(child_sizes)
+        }
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let mut subdivider = constraints.into_width_subdivider(maps.store);
+
+        for (child, slot) in maps.child_slots(entity) {
+            subdivider.subdivide_for_entity(&child, slot);
+        }
+
+        Arrangement::new(subdivider.iter().collect())
+            .with_diagnostics(LayoutDiagnostics {
+                overflow_width: subdivider.overflow(),
+                overflow_height: measure_height_overflow(maps, entity, constraints)
+            })
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct VerticalLayout {
+}
+
+impl LayoutAlgorithm for VerticalLayout {
+    fn measure(&self, _: &MeasurementMaps, _: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        match axis {
+            LayoutAxis::Horizontal => max_of_sizes(child_sizes),
+            LayoutAxis::Vertical => sum_of_sizes(child_sizes)
+        }
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let mut subdivider = constraints.into_height_subdivider(maps.store);
+
+        for (child, slot) in maps.child_slots(entity) {
+            subdivider.subdivide_for_entity(&child, slot);
+        }
+
+        Arrangement::new(subdivider.iter().collect())
+            .with_diagnostics(LayoutDiagnostics {
+                overflow_width: measure_width_overflow(maps, entity, constraints),
+                overflow_height: subdivider.overflow()
+            })
+    }
+}
+
+pub fn measure_width_overflow(maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> f32 {
+    maps
+        .child_slots(entity)
+        .filter_map(|(_, slot)| maps.store.sizes(slot).minimum_width)
+        .map(|minimum_width| minimum_width - constraints.width)
+        .fold(0.0, f32::max)
+}
+
+pub fn measure_height_overflow(maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> f32 {
+    maps
+        .child_slots(entity)
+        .filter_map(|(_, slot)| maps.store.sizes(slot).minimum_height)
+        .map(|minimum_height| minimum_height - constraints.height)
+        .fold(0.0, f32::max)
+}
@@ -0,0 +1,205 @@
+use std::collections::{ HashMap };
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::positioning::*;
+use crate::grid::*;
+use crate::wrapping::*;
+use crate::text::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct LayoutSizes {
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub minimum_width: Option<f32>,
+    pub minimum_height: Option<f32>,
+    pub sizing: Sizing,
+    pub visibility: Visibility
+}
+
+impl LayoutSizes {
+    pub fn wraps_content(&self) -> bool {
+        self.sizing == Sizing::WrapContent
+    }
+
+    pub fn fixed_size(&self, axis: LayoutAxis) -> Option<f32> {
+        match axis {
+            LayoutAxis::Horizontal => self.width,
+            LayoutAxis::Vertical => self.height
+        }
+    }
+
+    pub fn minimum_size(&self, axis: LayoutAxis) -> Option<f32> {
+        match axis {
+            LayoutAxis::Horizontal => self.minimum_width,
+            LayoutAxis::Vertical => self.minimum_height
+        }
+    }
+
+    pub fn set_minimum_size(&mut self, axis: LayoutAxis, size: f32) {
+        let minimum = if size > 0.0 { Some(size) } else { None };
+        match axis {
+            LayoutAxis::Horizontal => self.minimum_width = minimum,
+            LayoutAxis::Vertical => self.minimum_height = minimum
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct LayoutNode {
+    pub layout_type: Option<LayoutType>,
+    pub left: Option<f32>,
+    pub top: Option<f32>,
+    pub anchors: Anchors,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub grid_definition: GridDefinition,
+    pub grid_placement: GridPlacement,
+    pub wrap_definition: WrapDefinition,
+    pub dock: Dock,
+    pub alignment: Alignment,
+    pub direction: Option<Direction>,
+    pub text_format: Option<TextFormat>,
+    pub text_style: TextStyle,
+    pub content: Option<String>,
+    pub text_size: Option<TextSize>
+}
+
+impl LayoutNode {
+    pub fn is_span(&self) -> bool {
+        self.text_format.is_none() && self.content.is_some()
+    }
+
+    pub fn intrinsic_size(&self, axis: LayoutAxis) -> Option<f32> {
+        self.text_size.map(|text_size| text_size.size(axis))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LayoutSlot(usize);
+
+#[derive(Default, Debug)]
+pub struct LayoutNodeStore {
+    sizes: Vec<LayoutSizes>,
+    nodes: Vec<LayoutNode>,
+    slots: HashMap<Entity, usize>,
+    free_slots: Vec<usize>
+}
+
+pub fn create_layout_node_store() -> LayoutNodeStore {
+    LayoutNodeStore::default()
+}
+
+impl LayoutNodeStore {
+    pub fn slot(&self, entity: &Entity) -> Option<LayoutSlot> {
+        self.slots.get(entity).map(|slot| LayoutSlot(*slot))
+    }
+
+    pub fn slot_or_insert(&mut self, entity: Entity) -> LayoutSlot {
+        match self.slots.get(&entity) {
+            Some(slot) => LayoutSlot(*slot),
+            None => {
+                let slot = self.allocate();
+                self.slots.insert(entity, slot);
+                LayoutSlot(slot)
+            }
+        }
+    }
+
+    pub fn node(&self, slot: LayoutSlot) -> &LayoutNode {
+        &self.nodes[slot.0]
+    }
+
+    pub fn node_mut(&mut self, slot: LayoutSlot) -> &mut LayoutNode {
+        &mut self.nodes[slot.0]
+    }
+
+    pub fn sizes(&self, slot: LayoutSlot) -> &LayoutSizes {
+        &self.sizes[slot.0]
+    }
+
+    pub fn sizes_mut(&mut self, slot: LayoutSlot) -> &mut LayoutSizes {
+        &mut self.sizes[slot.0]
+    }
+
+    pub fn get(&self, entity: &Entity) -> Option<&LayoutNode> {
+        self.slot(entity).map(|slot| self.node(slot))
+    }
+
+    pub fn get_mut(&mut self, entity: &Entity) -> Option<&mut LayoutNode> {
+        match self.slot(entity) {
+            Some(slot) => Some(self.node_mut(slot)),
+            None => None
+        }
+    }
+
+    pub fn get_or_insert(&mut self, entity: Entity) -> &mut LayoutNode {
+        let slot = self.slot_or_insert(entity);
+        self.node_mut(slot)
+    }
+
+    pub fn remove(&mut self, entity: &Entity) {
+        if let Some(slot) = self.slots.remove(entity) {
+            self.free_slots.push(slot);
+        }
+    }
+
+    pub fn direction(&self, slot: LayoutSlot) -> Option<Direction> {
+        self.node(slot).direction
+    }
+
+    pub fn inherited_direction(&self, relationship_map: &RelationshipMap, entity: &Entity) -> Direction {
+        std::iter::successors(Some(*entity), |current| relationship_map.get_parent(current))
+            .filter_map(|ancestor| self.slot(&ancestor))
+            .find_map(|slot| self.direction(slot))
+            .unwrap_or_default()
+    }
+
+    pub fn visibility(&self, slot: LayoutSlot) -> Visibility {
+        self.sizes(slot).visibility
+    }
+
+    pub fn minimum_size(&self, slot: LayoutSlot, axis: LayoutAxis) -> f32 {
+        self.sizes(slot).minimum_size(axis).unwrap_or(0.0)
+    }
+
+    pub fn paragraph_spans(&self, relationship_map: &RelationshipMap, entity: &Entity) -> Vec<TextSpan> {
+        let node = match self.get(entity) {
+            Some(node) => node,
+            None => return vec!()
+        };
+
+        let children = relationship_map
+            .get_children(entity)
+            .filter_map(|child| self.get(&child))
+            .filter(|child| child.is_span());
+
+        node.content
+            .iter()
+            .map(|content| TextSpan::new(content, node.text_style.clone()))
+            .chain(children.map(|child| TextSpan::new(child.content.as_deref().unwrap_or_default(), child.text_style.inherit(&node.text_style))))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    fn allocate(&mut self) -> usize {
+        match self.free_slots.pop() {
+            Some(slot) => {
+                self.sizes[slot] = LayoutSizes::default();
+                self.nodes[slot] = LayoutNode::default();
+                slot
+            },
+            None => {
+                self.sizes.push(LayoutSizes::default());
+                self.nodes.push(LayoutNode::default());
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use legion::*;
+use log::{debug, info};
+use legion::systems::*;
+use zodiac::*;
+use crate::debugging::*;
+
+#[system(for_each)]
+#[filter(!component::<Removed>() & !component::<LayoutOutline>())]
+pub fn outline_layout(
+    #[resource] outlines: &mut LayoutOutlineMap,
+    command_buffer: &mut CommandBuffer,
+    entity: &Entity,
+    constraints: &CurrentLayoutConstraints,
+    layout: Option<&LayoutContent>,
+    diagnostics: Option<&LayoutDiagnostics>) {
+        let overflowed = diagnostics.map_or(false, |diagnostics| diagnostics.has_overflowed());
+        let change = LayoutChange {
+            left: constraints.left,
+            top: constraints.top,
+            width: constraints.width,
+            height: constraints.height
+        };
+        let (colour, width) = if overflowed {
+            (overflow_outline_colour(), StrokeWidth::from(2))
+        } else {
+            (outline_colour(layout.map(|layout| layout.layout_type)), StrokeWidth::from(1))
+        };
+
+        match outlines.get(entity).copied() {
+            Some(outlined) => {
+                if outlined.change != change || outlined.colour != colour || outlined.width != width {
+                    command_buffer.add_component(outlined.outline, change);
+                    command_buffer.add_component(outlined.outline, colour);
+                    command_buffer.add_component(outlined.outline, width);
+                    outlines.insert(*entity, OutlinedBox { change, colour, width, ..outlined });
+                }
+            },
+            None => {
+                let outline = command_buffer.push((LayoutOutline::default(), Renderable::default(), Mapped::default(), change, colour, width));
+                outlines.insert(*entity, OutlinedBox { outline, change, colour, width });
+            }
+        }
+}
+
+#[system(for_each)]
+#[filter(component::<Removed>())]
+pub fn remove_layout_outline(
+    #[resource] outlines: &mut LayoutOutlineMap,
+    command_buffer: &mut CommandBuffer,
+    entity: &Entity) {
+        if let Some(outlined) = outlines.remove(entity) {
+            debug!("removing layout outline for {:?}", entity);
+            command_buffer.remove(outlined.outline);
+        }
+}
+
+#[system(for_each)]
+#[filter(component::<LayoutChange>())]
+pub fn request_layout_dump(#[resource] request: &mut LayoutDumpRequest, _: &Entity) {
+    request.requested = true;
+}
+
+pub fn log_layout_dump(world: &mut World, resources: &mut Resources) {
+    let mut request = resources.get_mut::<LayoutDumpRequest>().unwrap();
+    if !request.requested {
+        return;
+    }
+    request.requested = false;
+    let relationship_map = resources.get::<RelationshipMap>().unwrap();
+    info!("layout dump\n{}", dump_layout(world, &relationship_map));
+}
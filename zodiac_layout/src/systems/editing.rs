@@ -0,0 +1,241 @@
+use legion::*;
+use legion::systems::*;
+use legion::world::*;
+use shrev::EventChannel;
+use zodiac::*;
+use crate::events::*;
+use crate::invalidation::*;
+use crate::store::*;
+
+#[system(for_each)]
+#[filter(component::<Editable>() & !component::<TextEditState>())]
+pub fn initialise_text_edit_state(
+    command_buffer: &mut CommandBuffer,
+    entity: &Entity,
+    value: Option<&Value>,
+    placeholder: Option<&Placeholder>) {
+        let mut text_edit_state = TextEditState::default();
+        if let Some(value) = value {
+            text_edit_state.bind(&value.text);
+        }
+        command_buffer.add_component(*entity, Content::from(text_edit_state.display_text(placeholder)));
+        command_buffer.add_component(*entity, text_edit_state);
+}
+
+fn contains(layout_box: &LayoutBox, left: f32, top: f32) -> bool {
+    let change = &layout_box.change;
+    layout_box.visible
+        && left >= change.left && left < change.left + change.width
+        && top >= change.top && top < change.top + change.height
+}
+
+#[system(simple)]
+#[read_component(Value)]
+#[read_component(Placeholder)]
+#[read_component(MultiLine)]
+#[read_component(OnChange)]
+#[read_component(OnSubmit)]
+#[read_component(TextLines)]
+#[write_component(TextEditState)]
+pub fn edit_text_inputs(
+    world: &mut SubWorld,
+    command_buffer: &mut CommandBuffer,
+    #[resource] event_channel: &mut EventChannel::<SystemEvent>,
+    #[resource] event_readers: &mut LayoutEventReaderRegistry,
+    #[resource] layout_boxes: &LayoutBoxMap,
+    #[resource] clipboard: &ApplicationClipboard,
+    #[resource] state_updates: &mut StateUpdateQueue) {
+        let inputs: Vec<SystemInputEventType> = event_channel
+            .read(&mut event_readers.text_input)
+            .filter_map(|event| match event {
+                SystemEvent::Input(input) => Some(input.clone()),
+                _ => None
+            })
+            .collect();
+
+        let mut query = <(Entity, Option<&Value>, Option<&Placeholder>, Option<&MultiLine>, Option<&OnChange>, Option<&OnSubmit>, Option<&TextLines>, &mut TextEditState)>::query();
+
+        for (entity, value, placeholder, multi_line, on_change, on_submit, text_lines, text_edit_state) in query.iter_mut(world) {
+            let multi_line = multi_line.map_or(false, |multi_line| multi_line.multi_line);
+            let mut rebuild = value.map_or(false, |value| text_edit_state.bind(&value.text));
+            let mut changed = false;
+            let mut composed = false;
+
+            for input in &inputs {
+                let current_lines = text_lines.filter(|_| !changed && !composed);
+                let accepts_keys = text_edit_state.focused && !text_edit_state.is_composing();
+                match input {
+                    SystemInputEventType::PointerPressed(left, top, modifiers) => {
+                        let (left, top) = (*left, *top);
+                        match layout_boxes.get(entity).filter(|layout_box| contains(layout_box, left, top)) {
+                            Some(layout_box) => {
+                                let cancelled = text_edit_state.cancel_composition();
+                                composed |= cancelled;
+                                let caret = current_lines.filter(|_| !cancelled).map_or(text_edit_state.value.len(), |lines| caret_at(
+                                    lines,
+                                    left - layout_box.change.left + text_edit_state.scroll_left,
+                                    top - layout_box.change.top + text_edit_state.scroll_top));
+                                text_edit_state.focus();
+                                text_edit_state.move_caret(caret, modifiers.shift);
+                                rebuild = true;
+                            },
+                            None if text_edit_state.focused => {
+                                composed |= text_edit_state.is_composing();
+                                text_edit_state.blur();
+                                rebuild = true;
+                            },
+                            None => {}
+                        }
+                    },
+                    SystemInputEventType::KeyPressed(KeyCode::Letter('c'), modifiers) if accepts_keys && modifiers.control => {
+                        if text_edit_state.has_selection() {
+                            clipboard.set_text(text_edit_state.selected_text());
+                        }
+                    },
+                    SystemInputEventType::KeyPressed(KeyCode::Letter('v'), modifiers) if accepts_keys && modifiers.control => {
+                        if let Some(text) = clipboard.get_text() {
+                            changed |= text_edit_state.apply_text(&text);
+                            rebuild = true;
+                        }
+                    },
+                    SystemInputEventType::KeyPressed(key, modifiers) if accepts_keys => {
+                        let outcome = text_edit_state.apply_key(*key, *modifiers, current_lines, multi_line);
+                        if outcome.submitted {
+                            if let Some(on_submit) = on_submit {
+                                state_updates.push(on_submit.handler.update_for(&text_edit_state.value));
+                            }
+                        }
+                        changed |= outcome.changed;
+                        rebuild = true;
+                    },
+                    SystemInputEventType::TextReceived(text) if accepts_keys => {
+                        changed |= text_edit_state.apply_text(text);
+                        rebuild = true;
+                    },
+                    SystemInputEventType::CompositionUpdated(text, cursor) if text_edit_state.focused => {
+                        changed |= text_edit_state.update_composition(text, *cursor);
+                        composed = true;
+                        rebuild = true;
+                    },
+                    SystemInputEventType::CompositionCommitted(text) if text_edit_state.focused => {
+                        changed |= text_edit_state.commit_composition(text);
+                        composed = true;
+                        rebuild = true;
+                    },
+                    SystemInputEventType::CompositionCancelled if text_edit_state.focused => {
+                        composed |= text_edit_state.cancel_composition();
+                        rebuild = true;
+                    },
+                    _ => {}
+                }
+            }
+
+            if changed {
+                if let Some(on_change) = on_change {
+                    state_updates.push(on_change.handler.update_for(text_edit_state.emit()));
+                }
+            }
+
+            if rebuild {
+                command_buffer.add_component(*entity, Content::from(text_edit_state.display_text(placeholder)));
+                command_buffer.add_component(*entity, Rebuild::default());
+            }
+        }
+}
+
+#[system(for_each)]
+#[filter(!component::<TextSelection>())]
+pub fn initialise_text_selection(command_buffer: &mut CommandBuffer, entity: &Entity, selectable: &Selectable) {
+    if selectable.selectable {
+        command_buffer.add_component(*entity, TextSelection::default());
+    }
+}
+
+#[system(for_each)]
+#[filter(component::<TextSelection>())]
+pub fn remove_text_selection(command_buffer: &mut CommandBuffer, entity: &Entity, selectable: &Selectable) {
+    if !selectable.selectable {
+        command_buffer.remove_component::<TextSelection>(*entity);
+        command_buffer.add_component(*entity, Rebuild::default());
+    }
+}
+
+fn pointer_caret(lines: &TextLines, layout_box: &LayoutBox, left: f32, top: f32) -> usize {
+    let change = &layout_box.change;
+    caret_at(
+        lines,
+        (left - change.left).max(0.0).min(change.width),
+        (top - change.top).max(0.0).min(change.height))
+}
+
+#[system(simple)]
+#[read_component(Selectable)]
+#[read_component(TextLines)]
+#[write_component(TextSelection)]
+pub fn select_text(
+    world: &mut SubWorld,
+    command_buffer: &mut CommandBuffer,
+    #[resource] event_channel: &mut EventChannel::<SystemEvent>,
+    #[resource] event_readers: &mut LayoutEventReaderRegistry,
+    #[resource] layout_boxes: &LayoutBoxMap,
+    #[resource] store: &LayoutNodeStore,
+    #[resource] relationship_map: &RelationshipMap,
+    #[resource] clipboard: &ApplicationClipboard) {
+        let inputs: Vec<SystemInputEventType> = event_channel
+            .read(&mut event_readers.text_selection)
+            .filter_map(|event| match event {
+                SystemEvent::Input(input) => Some(input.clone()),
+                _ => None
+            })
+            .collect();
+
+        if inputs.is_empty() {
+            return;
+        }
+
+        let mut query = <(Entity, &Selectable, &TextLines, &mut TextSelection)>::query();
+
+        for (entity, selectable, text_lines, text_selection) in query.iter_mut(world) {
+            if !selectable.selectable {
+                continue;
+            }
+            let layout_box = match layout_boxes.get(entity).filter(|layout_box| layout_box.visible) {
+                Some(layout_box) => layout_box,
+                None => continue
+            };
+            let mut rebuild = false;
+
+            for input in &inputs {
+                match input {
+                    SystemInputEventType::PointerPressed(left, top, modifiers) => {
+                        if contains(layout_box, *left, *top) {
+                            rebuild |= text_selection.select(pointer_caret(text_lines, layout_box, *left, *top), modifiers.shift);
+                            text_selection.dragging = true;
+                        } else {
+                            rebuild |= text_selection.clear();
+                        }
+                    },
+                    SystemInputEventType::PointerMoved(left, top, _) if text_selection.dragging => {
+                        rebuild |= text_selection.select(pointer_caret(text_lines, layout_box, *left, *top), true);
+                    },
+                    SystemInputEventType::PointerReleased(left, top, _) if text_selection.dragging => {
+                        rebuild |= text_selection.select(pointer_caret(text_lines, layout_box, *left, *top), true);
+                        text_selection.dragging = false;
+                    },
+                    SystemInputEventType::KeyPressed(KeyCode::Letter('c'), modifiers) if modifiers.control && text_selection.has_selection() => {
+                        let text: String = store
+                            .paragraph_spans(relationship_map, entity)
+                            .iter()
+                            .map(|span| span.text.as_str())
+                            .collect();
+                        clipboard.set_text(text_selection.selected_text(&text));
+                    },
+                    _ => {}
+                }
+            }
+
+            if rebuild {
+                command_buffer.add_component(*entity, Rebuild::default());
+            }
+        }
+}
@@ -0,0 +1,30 @@
+use legion::*;
+use log::{debug};
+use zodiac::*;
+use crate::invalidation::*;
+
+#[system(for_each)]
+#[filter((!component::<Mapped>() | component::<Rebuild>()) & !component::<Removed>())]
+pub fn mark_layout_dirty(#[resource] dirty_set: &mut LayoutDirtySet, entity: &Entity) {
+    dirty_set.mark(*entity);
+}
+
+#[system(for_each)]
+#[filter(component::<Removed>())]
+pub fn mark_removed_parent_layout_dirty(#[resource] dirty_set: &mut LayoutDirtySet, relationship: &Relationship) {
+    if let Some(parent) = relationship.parent {
+        dirty_set.mark(parent);
+    }
+}
+
+#[system(for_each)]
+#[filter(component::<Removed>())]
+pub fn remove_from_layout_box_map(#[resource] layout_boxes: &mut LayoutBoxMap, entity: &Entity) {
+    debug!("removing from layout box map {:?}", entity);
+    layout_boxes.remove(entity);
+}
+
+#[system(simple)]
+pub fn clear_layout_dirty_set(#[resource] dirty_set: &mut LayoutDirtySet) {
+    dirty_set.clear();
+}
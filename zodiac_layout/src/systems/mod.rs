@@ -1,4 +1,7 @@
-
+pub mod store;
 pub mod measurement;
 pub mod resizing;
-pub mod positioning;
+pub mod invalidation;
+pub mod debugging;
+pub mod text;
+pub mod editing;
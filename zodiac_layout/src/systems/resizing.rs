@@ -7,10 +7,11 @@ use shrev::EventChannel;
 use zodiac::*;
 
 use crate::events::*;
-use crate::measurement::*;
-use crate::positioning::*;
 use crate::resizing::*;
 use crate::constraints::*;
+use crate::algorithms::*;
+use crate::invalidation::*;
+use crate::store::*;
 
 pub fn request_root_layout(
     world: &mut SubWorld,
@@ -30,13 +31,15 @@ pub fn resize_screen(
     world: &mut SubWorld,
     command_buffer: &mut CommandBuffer,
     #[resource] event_channel: &mut EventChannel::<SystemEvent>,
-    #[resource] event_readers: &mut LayoutEventReaderRegistry
+    #[resource] event_readers: &mut LayoutEventReaderRegistry,
+    #[resource] layout_boxes: &mut LayoutBoxMap
 ) {
     for event in event_channel.read(&mut event_readers.resize_screen) {
         match event {
             SystemEvent::Window(SystemWindowEventType::RootWindowResize(dimensions)) => {
                 debug!("root window resize recieved");
                 debug!("requesting root layout");
+                layout_boxes.clear();
                 request_root_layout(world, command_buffer, &dimensions);
             },
             _ => {}
@@ -44,52 +47,87 @@ pub fn resize_screen(
     }
 }
 
-#[system(for_each)]
-#[filter(component::<Rebuild>())]
-#[filter(!component::<LayoutRequest>())]
-pub fn resize_after_rebuild(
-    command_buffer: &mut CommandBuffer,
-    entity: &Entity,
-    current_layout_constraints: &CurrentLayoutConstraints
-) {
-    debug!("source file change {:?}", current_layout_constraints);
-    command_buffer.add_component(*entity, LayoutRequest::from(current_layout_constraints));
-}
-
 #[system(for_each)]
 pub fn resize(
     #[resource] relationship_map: &RelationshipMap,
-    #[resource] layout_map: &LayoutTypeMap,
-    #[resource] left_map: &LeftOffsetMap,
-    #[resource] top_map: &TopOffsetMap,
-    #[resource] width_map: &WidthMap,
-    #[resource] minimum_width_map: &MinimumWidthMap,
-    #[resource] height_map: &HeightMap,
-    #[resource] minimum_height_map: &MinimumHeightMap,
-    world: &mut SubWorld,
+    #[resource] store: &LayoutNodeStore,
+    #[resource] algorithms: &LayoutAlgorithmRegistry,
+    #[resource] dirty_set: &LayoutDirtySet,
+    #[resource] layout_boxes: &mut LayoutBoxMap,
     command_buffer: &mut CommandBuffer,
     entity: &Entity,
     resize_request: &LayoutRequest) {
         perform_resize(
             &LayoutMaps {
-                relationship_map, 
-                layout_map, 
-                left_map,
-                top_map,
-                width_map, 
-                minimum_width_map,
-                height_map,
-                minimum_height_map,
+                relationship_map,
+                store,
+                algorithms,
+                dirty_set
             },
-            world,
             command_buffer,
+            layout_boxes,
             entity,
             &LayoutConstraints::from(resize_request));
 }
 
+fn is_layout_requested(world: &SubWorld, relationship_map: &RelationshipMap, entity: &Entity) -> bool {
+    let mut current = Some(*entity);
+    while let Some(ancestor) = current {
+        if let Ok(entry) = world.entry_ref(ancestor) {
+            if entry.get_component::<LayoutRequest>().is_ok() {
+                return true;
+            }
+        }
+        current = relationship_map.get_parent(&ancestor);
+    }
+    false
+}
+
+fn get_current_constraints(world: &SubWorld, entity: &Entity) -> Option<LayoutConstraints> {
+    world
+        .entry_ref(*entity)
+        .ok()
+        .and_then(|entry| entry.get_component::<CurrentLayoutConstraints>().ok().map(LayoutConstraints::from))
+}
+
+#[system(simple)]
+#[read_component(LayoutRequest)]
+#[read_component(CurrentLayoutConstraints)]
+pub fn relayout_dirty(
+    #[resource] relationship_map: &RelationshipMap,
+    #[resource] store: &LayoutNodeStore,
+    #[resource] algorithms: &LayoutAlgorithmRegistry,
+    #[resource] dirty_set: &LayoutDirtySet,
+    #[resource] layout_boxes: &mut LayoutBoxMap,
+    world: &mut SubWorld,
+    command_buffer: &mut CommandBuffer) {
+        let maps = LayoutMaps {
+            relationship_map,
+            store,
+            algorithms,
+            dirty_set
+        };
+
+        for root in dirty_set.relayout_roots(relationship_map) {
+            if is_layout_requested(world, relationship_map, &root) || maps.is_collapsed(&root) {
+                continue;
+            }
+            if let Some(constraints) = get_current_constraints(world, &root) {
+                debug!("relayout of dirty subtree {:?}", root);
+                perform_layout(&maps, command_buffer, layout_boxes, &root, &constraints);
+            }
+        }
+}
+
 #[system(for_each)]
 #[filter(component::<Resized>())]
 pub fn remove_resized(command_buffer: &mut CommandBuffer, entity: &Entity) {
     command_buffer.remove_component::<Resized>(*entity);
     command_buffer.remove_component::<Mapped>(*entity);
-}
\ No newline at end of file
+}
+
+#[system(for_each)]
+#[filter(component::<LayoutChange>())]
+pub fn remove_layout_change(command_buffer: &mut CommandBuffer, entity: &Entity) {
+    command_buffer.remove_component::<LayoutChange>(*entity);
+}
@@ -0,0 +1,181 @@
+use legion::*;
+use log::{debug};
+use zodiac::*;
+use crate::positioning::*;
+use crate::grid::*;
+use crate::wrapping::*;
+use crate::store::*;
+use crate::text::*;
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_positions(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    left: Option<&Left>,
+    top: Option<&Top>,
+    right: Option<&Right>,
+    bottom: Option<&Bottom>,
+    center_x: Option<&CenterX>,
+    center_y: Option<&CenterY>) {
+        let node = store.get_or_insert(*entity);
+        node.left = left.map(|left| left.left);
+        node.top = top.map(|top| top.top);
+        node.anchors = Anchors {
+            right: right.map(|right| right.right),
+            bottom: bottom.map(|bottom| bottom.bottom),
+            center_x: center_x.map(|center_x| center_x.offset),
+            center_y: center_y.map(|center_y| center_y.offset)
+        };
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_sizes(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    width: Option<&Width>,
+    height: Option<&Height>,
+    radius: Option<&Radius>,
+    sizing: Option<&Sizing>,
+    aspect_ratio: Option<&AspectRatio>) {
+        let slot = store.slot_or_insert(*entity);
+        let sizes = store.sizes_mut(slot);
+        sizes.width = width.map(|width| width.width).or_else(|| radius.map(|radius| Width::from(radius).width));
+        sizes.height = height.map(|height| height.height).or_else(|| radius.map(|radius| Height::from(radius).height));
+        sizes.sizing = sizing.copied().unwrap_or_default();
+        store.node_mut(slot).aspect_ratio = aspect_ratio.copied();
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_content(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    layout: Option<&LayoutContent>,
+    flow: Option<&Flow>,
+    line_spacing: Option<&LineSpacing>,
+    line_alignment: Option<&LineAlignment>,
+    direction: Option<&Direction>,
+    visibility: Option<&Visibility>) {
+        let slot = store.slot_or_insert(*entity);
+        let node = store.node_mut(slot);
+        node.layout_type = layout.map(|layout| layout.layout_type);
+        node.direction = direction.copied();
+        node.wrap_definition = WrapDefinition {
+            flow: flow.copied().unwrap_or_default(),
+            line_spacing: line_spacing.map_or(0.0, |line_spacing| line_spacing.spacing),
+            line_alignment: line_alignment.copied().unwrap_or_default()
+        };
+        store.sizes_mut(slot).visibility = visibility.copied().unwrap_or_default();
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_grid_definition(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    rows: Option<&Rows>,
+    columns: Option<&Columns>,
+    row_gap: Option<&RowGap>,
+    column_gap: Option<&ColumnGap>) {
+        store.get_or_insert(*entity).grid_definition = GridDefinition {
+            rows: rows.map_or_else(Vec::new, |rows| rows.tracks.clone()),
+            columns: columns.map_or_else(Vec::new, |columns| columns.tracks.clone()),
+            row_gap: row_gap.map_or(0.0, |row_gap| row_gap.gap),
+            column_gap: column_gap.map_or(0.0, |column_gap| column_gap.gap)
+        };
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_placement(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    row: Option<&Row>,
+    column: Option<&Column>,
+    row_span: Option<&RowSpan>,
+    column_span: Option<&ColumnSpan>,
+    dock: Option<&Dock>,
+    alignment: Option<&Alignment>) {
+        let node = store.get_or_insert(*entity);
+        node.grid_placement = GridPlacement {
+            row: row.map_or(0, |row| row.row),
+            column: column.map_or(0, |column| column.column),
+            row_span: row_span.map_or(1, |row_span| row_span.span),
+            column_span: column_span.map_or(1, |column_span| column_span.span)
+        };
+        node.dock = dock.copied().unwrap_or_default();
+        node.alignment = alignment.copied().unwrap_or_default();
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_text(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    renderable: Option<&Renderable>,
+    font_size: Option<&FontSize>,
+    wrap: Option<&TextWrap>,
+    align: Option<&TextAlign>,
+    line_height: Option<&LineHeight>,
+    max_lines: Option<&MaxLines>) {
+        store.get_or_insert(*entity).text_format = renderable.and(font_size).map(|_| TextFormat {
+            wrap: wrap.copied().unwrap_or_default(),
+            align: align.copied().unwrap_or_default(),
+            line_height: line_height.map(|line_height| line_height.height),
+            max_lines: max_lines.map(|max_lines| max_lines.lines)
+        });
+}
+
+#[system(for_each)]
+#[filter((!component::<Mapped>() | component::<Rebuild>()) & !component::<TextWrap>())]
+pub fn build_layout_node_multi_line_wrap(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    multi_line: &MultiLine) {
+        if let Some(text_format) = store.get_or_insert(*entity).text_format.as_mut() {
+            if multi_line.multi_line {
+                text_format.wrap = TextWrap::Word;
+            }
+        }
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_text_style(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    content: Option<&Content>,
+    font_size: Option<&FontSize>,
+    family: Option<&FontFamily>,
+    weight: Option<&FontWeight>,
+    style: Option<&FontStyle>,
+    colour: Option<&Colour>) {
+        let node = store.get_or_insert(*entity);
+        node.content = content.map(|content| content.text.clone());
+        node.text_style.font_size = font_size.map(|font_size| font_size.size as f32);
+        node.text_style.family = family.map(|family| family.family.clone());
+        node.text_style.weight = weight.copied();
+        node.text_style.style = style.copied();
+        node.text_style.colour = colour.copied();
+}
+
+#[system(for_each)]
+#[filter(!component::<Mapped>() | component::<Rebuild>())]
+pub fn build_layout_node_text_decoration(
+    #[resource] store: &mut LayoutNodeStore,
+    entity: &Entity,
+    underline: Option<&Underline>,
+    background: Option<&Background>) {
+        let node = store.get_or_insert(*entity);
+        node.text_style.underline = underline.map(|underline| underline.underline);
+        node.text_style.background = background.map(|background| background.colour);
+}
+
+#[system(for_each)]
+#[filter(component::<Removed>())]
+pub fn remove_from_layout_node_store(#[resource] store: &mut LayoutNodeStore, entity: &Entity) {
+    debug!("removing from layout node store {:?}", entity);
+    store.remove(entity);
+}
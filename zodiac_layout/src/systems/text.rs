@@ -0,0 +1,34 @@
+use legion::*;
+use legion::systems::*;
+use zodiac::*;
+use crate::text::*;
+use crate::store::*;
+
+#[system(for_each)]
+pub fn layout_text_lines(
+    #[resource] store: &LayoutNodeStore,
+    #[resource] relationship_map: &RelationshipMap,
+    #[resource] fonts: &FontRegistry,
+    command_buffer: &mut CommandBuffer,
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    current_direction: &CurrentDirection,
+    text_edit_state: Option<&mut TextEditState>) {
+        if let Some(format) = store.get(entity).and_then(|node| node.text_format) {
+            let spans = store.paragraph_spans(relationship_map, entity);
+            let spans = match text_edit_state.as_ref().and_then(|state| state.composition_range()) {
+                Some((start, end)) => underline_range(spans, start, end),
+                None => spans
+            };
+            let text_lines = layout_text(
+                fonts,
+                &spans,
+                &format,
+                layout_change.width,
+                current_direction.direction);
+            if let Some(text_edit_state) = text_edit_state {
+                text_edit_state.scroll_to_caret(&text_lines, layout_change.width, layout_change.height);
+            }
+            command_buffer.add_component(*entity, text_lines);
+        }
+}
@@ -1,9 +1,542 @@
-use std::collections::{ HashMap };
-use legion::*;
 use zodiac::*;
+use crate::algorithms::*;
+use crate::shaping::*;
 
-pub type TextColourMap = HashMap<Entity, Colour>;
+const ELLIPSIS: char = '\u{2026}';
 
-pub fn create_text_colour_map() -> TextColourMap {
-    TextColourMap::new()
-}
\ No newline at end of file
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct TextSize {
+    pub width: f32,
+    pub height: f32
+}
+
+impl TextSize {
+    pub fn size(&self, axis: LayoutAxis) -> f32 {
+        match axis {
+            LayoutAxis::Horizontal => self.width,
+            LayoutAxis::Vertical => self.height
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct TextFormat {
+    pub wrap: TextWrap,
+    pub align: TextAlign,
+    pub line_height: Option<f32>,
+    pub max_lines: Option<u16>
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextStyle {
+    pub family: Option<String>,
+    pub weight: Option<FontWeight>,
+    pub style: Option<FontStyle>,
+    pub font_size: Option<f32>,
+    pub colour: Option<Colour>,
+    pub underline: Option<bool>,
+    pub background: Option<Colour>
+}
+
+impl TextStyle {
+    pub fn inherit(&self, parent: &TextStyle) -> TextStyle {
+        TextStyle {
+            family: self.family.clone().or_else(|| parent.family.clone()),
+            weight: self.weight.or(parent.weight),
+            style: self.style.or(parent.style),
+            font_size: self.font_size.or(parent.font_size),
+            colour: self.colour.or(parent.colour),
+            underline: self.underline.or(parent.underline),
+            background: self.background.or(parent.background)
+        }
+    }
+
+    pub fn font(&self) -> FontSelection {
+        FontSelection {
+            family: self.family.clone(),
+            weight: self.weight.unwrap_or_default(),
+            style: self.style.unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle
+}
+
+impl TextSpan {
+    pub fn new(text: &str, style: TextStyle) -> Self {
+        Self {
+            text: text.to_string(),
+            style
+        }
+    }
+}
+
+pub fn underline_range(spans: Vec<TextSpan>, start: usize, end: usize) -> Vec<TextSpan> {
+    let mut span_start = 0;
+    let mut underlined = vec!();
+
+    for span in spans {
+        let span_end = span_start + span.text.len();
+        if span.text.is_empty() || end <= span_start || start >= span_end {
+            span_start = span_end;
+            underlined.push(span);
+            continue;
+        }
+
+        let middle_start = start.max(span_start) - span_start;
+        let middle_end = end.min(span_end) - span_start;
+        let (before, rest) = span.text.split_at(middle_start);
+        let (middle, after) = rest.split_at(middle_end - middle_start);
+        if !before.is_empty() {
+            underlined.push(TextSpan::new(before, span.style.clone()));
+        }
+        underlined.push(TextSpan::new(middle, TextStyle { underline: Some(true), ..span.style.clone() }));
+        if !after.is_empty() {
+            underlined.push(TextSpan::new(after, span.style));
+        }
+        span_start = span_end;
+    }
+
+    underlined
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SpanStyle {
+    font: FontSelection,
+    font_size: f32,
+    colour: Option<Colour>,
+    underline: bool,
+    background: Option<Colour>,
+    has_text: bool
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct StyledChar {
+    character: char,
+    offset: usize,
+    span: usize,
+    font: FontId,
+    level: u8,
+    advance: f32,
+    glyphs: Vec<ShapedGlyph>
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct BrokenLine {
+    chars: Vec<StyledChar>,
+    offset: usize,
+    width: f32,
+    ends_paragraph: bool
+}
+
+pub fn measure_text(fonts: &FontRegistry, spans: &[TextSpan], format: &TextFormat, available_width: f32, direction: Direction) -> TextSize {
+    let measurer = TextMeasurer::new(fonts, spans, format, direction);
+    let lines = measurer.break_lines(&measurer.styled_chars(spans), available_width);
+    TextSize {
+        width: lines.iter().fold(0.0, |width, line| line.width.max(width)),
+        height: lines.len() as f32 * measurer.line_height()
+    }
+}
+
+pub fn layout_text(fonts: &FontRegistry, spans: &[TextSpan], format: &TextFormat, width: f32, direction: Direction) -> TextLines {
+    let measurer = TextMeasurer::new(fonts, spans, format, direction);
+    let line_height = measurer.line_height();
+    let mut runs = vec!();
+
+    for (index, line) in measurer.break_lines(&measurer.styled_chars(spans), width).iter().enumerate() {
+        let top = index as f32 * line_height;
+        let extra = (width - line.width).max(0.0);
+        let spaces = line.chars.iter().filter(|styled| styled.character == ' ').count();
+        let levels: Vec<u8> = line.chars.iter().map(|styled| styled.level).collect();
+        let visual: Vec<&StyledChar> = visual_order(&levels).into_iter().map(|index| &line.chars[index]).collect();
+
+        if format.align == TextAlign::Justify && !line.ends_paragraph && spaces > 0 {
+            measurer.push_runs(&mut runs, &visual, line.offset, 0.0, top, Some(extra / spaces as f32));
+        } else {
+            measurer.push_runs(&mut runs, &visual, line.offset, align_line(format.align, direction, extra), top, None);
+        }
+    }
+
+    TextLines {
+        runs,
+        line_height,
+        baseline: measurer.baseline(line_height)
+    }
+}
+
+struct TextMeasurer<'a> {
+    fonts: &'a FontRegistry,
+    format: &'a TextFormat,
+    direction: Direction,
+    spans: Vec<SpanStyle>
+}
+
+impl<'a> TextMeasurer<'a> {
+    fn new(fonts: &'a FontRegistry, spans: &[TextSpan], format: &'a TextFormat, direction: Direction) -> Self {
+        let mut styles: Vec<SpanStyle> = spans
+            .iter()
+            .map(|span| SpanStyle {
+                font: span.style.font(),
+                font_size: span.style.font_size.unwrap_or_default(),
+                colour: span.style.colour,
+                underline: span.style.underline.unwrap_or_default(),
+                background: span.style.background,
+                has_text: !span.text.is_empty()
+            })
+            .collect();
+
+        if styles.is_empty() {
+            styles.push(SpanStyle {
+                font: FontSelection::default(),
+                font_size: 0.0,
+                colour: None,
+                underline: false,
+                background: None,
+                has_text: false
+            });
+        }
+
+        Self {
+            fonts,
+            format,
+            direction,
+            spans: styles
+        }
+    }
+
+    fn styled_chars(&self, spans: &[TextSpan]) -> Vec<StyledChar> {
+        let mut offset = 0;
+        let mut chars = vec!();
+        for (span, text_span) in spans.iter().enumerate() {
+            for (index, character) in text_span.text.char_indices() {
+                chars.push(self.styled_char(character, offset + index, span));
+            }
+            offset += text_span.text.len();
+        }
+        chars
+    }
+
+    fn styled_char(&self, character: char, offset: usize, span: usize) -> StyledChar {
+        StyledChar {
+            character,
+            offset,
+            span,
+            font: self.fonts.resolve_for_character(&self.spans[span].font, character),
+            level: 0,
+            advance: 0.0,
+            glyphs: vec!()
+        }
+    }
+
+    fn shape(&self, paragraph: &[StyledChar]) -> Vec<StyledChar> {
+        let text: String = paragraph.iter().map(|styled| styled.character).collect();
+        let mut shaped: Vec<StyledChar> = paragraph
+            .iter()
+            .zip(bidi_levels(&text, self.direction))
+            .map(|(styled, level)| StyledChar { level, ..styled.clone() })
+            .collect();
+
+        let mut start = 0;
+        while start < shaped.len() {
+            let end = shaped[start..]
+                .iter()
+                .position(|styled| !same_run(styled, &shaped[start]))
+                .map_or(shaped.len(), |length| start + length);
+            self.shape_item(&mut shaped[start..end]);
+            start = end;
+        }
+
+        shaped
+    }
+
+    fn shape_item(&self, item: &mut [StyledChar]) {
+        let text: String = item.iter().map(|styled| styled.character).collect();
+        let offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+        let face = self.fonts.face(item[0].font);
+        let font_size = self.spans[item[0].span].font_size;
+
+        for styled in item.iter_mut() {
+            styled.advance = 0.0;
+            styled.glyphs.clear();
+        }
+
+        for glyph in shape_text(face, &text, level_direction(item[0].level), font_size) {
+            let index = offsets
+                .binary_search(&glyph.cluster)
+                .unwrap_or_else(|index| index.saturating_sub(1));
+            item[index].advance += glyph.advance;
+            item[index].glyphs.push(glyph);
+        }
+    }
+
+    fn measure(&self, chars: &[StyledChar]) -> f32 {
+        chars.iter().map(|styled| styled.advance).sum()
+    }
+
+    fn push_runs(&self, runs: &mut Vec<TextRun>, chars: &[&StyledChar], offset: usize, left: f32, top: f32, justify_gap: Option<f32>) {
+        let first_run = runs.len();
+        let mut pen = left;
+        let mut run_left = left;
+        let mut run: Vec<&StyledChar> = vec!();
+
+        for styled in chars {
+            let separator = justify_gap.filter(|_| styled.character == ' ');
+            let continues = run.last().map_or(true, |last| same_run(last, styled));
+            if !run.is_empty() && (separator.is_some() || !continues) {
+                runs.push(self.run(&run, offset, run_left, top));
+                run.clear();
+            }
+            if let Some(gap) = separator {
+                pen += styled.advance + gap;
+                continue;
+            }
+            if run.is_empty() {
+                run_left = pen;
+            }
+            run.push(styled);
+            pen += styled.advance;
+        }
+
+        if !run.is_empty() || runs.len() == first_run {
+            runs.push(self.run(&run, offset, run_left, top));
+        }
+    }
+
+    fn run(&self, visual: &[&StyledChar], offset: usize, left: f32, top: f32) -> TextRun {
+        let (span, font, level) = visual
+            .first()
+            .map_or_else(|| (0, self.fonts.resolve(&self.spans[0].font), 0), |styled| (styled.span, styled.font, styled.level));
+        let direction = level_direction(level);
+        let style = &self.spans[span];
+
+        let mut logical: Vec<&StyledChar> = visual.to_vec();
+        if direction == Direction::RightToLeft {
+            logical.reverse();
+        }
+        let text: String = logical.iter().map(|styled| styled.character).collect();
+        let clusters: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+
+        let mut glyphs = vec!();
+        let mut pen = 0.0;
+        for (index, styled) in visual.iter().enumerate() {
+            let cluster = match direction {
+                Direction::LeftToRight => clusters[index],
+                Direction::RightToLeft => clusters[clusters.len() - 1 - index]
+            };
+            for glyph in &styled.glyphs {
+                glyphs.push(TextGlyph {
+                    id: glyph.id,
+                    cluster,
+                    left: pen + glyph.left,
+                    top: glyph.top,
+                    advance: glyph.advance
+                });
+                pen += glyph.advance;
+            }
+        }
+
+        TextRun {
+            text,
+            glyphs,
+            direction,
+            font,
+            font_size: style.font_size,
+            colour: style.colour,
+            underline: style.underline,
+            background: style.background,
+            offset: visual.iter().map(|styled| styled.offset).min().unwrap_or(offset),
+            left,
+            top,
+            width: pen
+        }
+    }
+
+    fn line_metrics(&self) -> (f32, f32) {
+        let mut styles: Vec<&SpanStyle> = self.spans.iter().filter(|style| style.has_text).collect();
+        if styles.is_empty() {
+            styles.push(&self.spans[0]);
+        }
+
+        styles
+            .iter()
+            .map(|style| {
+                let face = self.fonts.face(self.fonts.resolve(&style.font));
+                (face.line_height(style.font_size), face.ascent(style.font_size))
+            })
+            .fold((0.0, 0.0), |(line_height, ascent), (span_line_height, span_ascent)| {
+                (f32::max(line_height, span_line_height), f32::max(ascent, span_ascent))
+            })
+    }
+
+    fn line_height(&self) -> f32 {
+        self.format.line_height.unwrap_or_else(|| self.line_metrics().0)
+    }
+
+    fn baseline(&self, line_height: f32) -> f32 {
+        let (natural_line_height, ascent) = self.line_metrics();
+        let leading = (line_height - natural_line_height) / 2.0;
+        leading + ascent
+    }
+
+    fn break_lines(&self, chars: &[StyledChar], available_width: f32) -> Vec<BrokenLine> {
+        let mut lines = vec!();
+        let mut paragraph_offset = 0;
+        for paragraph in chars.split(|styled| styled.character == '\n') {
+            let next_paragraph_offset = paragraph
+                .last()
+                .map_or(paragraph_offset, |styled| styled.offset + styled.character.len_utf8()) + 1;
+            let paragraph = self.shape(paragraph);
+            let start = lines.len();
+            match self.format.wrap {
+                TextWrap::None => lines.push(self.broken_line(paragraph)),
+                TextWrap::Word => self.break_words(&paragraph, available_width, &mut lines),
+                TextWrap::Character => self.break_characters(&paragraph, available_width, &mut lines)
+            }
+            if lines.len() == start {
+                lines.push(self.broken_line(vec!()));
+            }
+            for line in lines[start..].iter_mut().filter(|line| line.chars.is_empty()) {
+                line.offset = paragraph_offset;
+            }
+            if let Some(last) = lines.last_mut() {
+                last.ends_paragraph = true;
+            }
+            paragraph_offset = next_paragraph_offset;
+        }
+
+        if let Some(max_lines) = self.format.max_lines.map(|max_lines| max_lines.max(1) as usize) {
+            let truncated = lines.len() > max_lines;
+            lines.truncate(max_lines);
+            if let Some(last) = lines.pop() {
+                if truncated || last.width > available_width {
+                    lines.push(self.ellipsise(&last.chars, available_width));
+                } else {
+                    lines.push(last);
+                }
+            }
+        }
+
+        lines
+    }
+
+    fn break_words(&self, paragraph: &[StyledChar], available_width: f32, lines: &mut Vec<BrokenLine>) {
+        let mut current: Vec<StyledChar> = vec!();
+        let mut current_width = 0.0;
+        for (separator, word) in words(paragraph) {
+            let separator = separator.filter(|_| !current.is_empty());
+            let separator_width = separator.map_or(0.0, |separator| separator.advance);
+            let word_width = self.measure(word);
+            if current_width + separator_width + word_width <= available_width {
+                current.extend(separator.cloned());
+                current.extend_from_slice(word);
+                current_width += separator_width + word_width;
+                continue;
+            }
+            if !current.is_empty() {
+                lines.push(self.broken_line(std::mem::take(&mut current)));
+            }
+            if word_width <= available_width {
+                current = word.to_vec();
+                current_width = word_width;
+            } else {
+                self.break_characters(word, available_width, lines);
+                current = lines.pop().map_or_else(Vec::new, |line| line.chars);
+                current_width = self.measure(&current);
+            }
+        }
+        if !current.is_empty() {
+            lines.push(self.broken_line(current));
+        }
+    }
+
+    fn break_characters(&self, paragraph: &[StyledChar], available_width: f32, lines: &mut Vec<BrokenLine>) {
+        let mut current: Vec<StyledChar> = vec!();
+        let mut current_width = 0.0;
+        for styled in paragraph {
+            if !current.is_empty() && current_width + styled.advance > available_width {
+                lines.push(self.broken_line(trim_end(&current).to_vec()));
+                current.clear();
+                current_width = 0.0;
+                if styled.character == ' ' {
+                    continue;
+                }
+            }
+            current.push(styled.clone());
+            current_width += styled.advance;
+        }
+        if !current.is_empty() {
+            lines.push(self.broken_line(current));
+        }
+    }
+
+    fn ellipsise(&self, chars: &[StyledChar], available_width: f32) -> BrokenLine {
+        let (offset, span, level) = chars.last().map_or((0, 0, 0), |styled| (styled.offset + styled.character.len_utf8(), styled.span, styled.level));
+        let mut ellipsis = vec!(StyledChar { level, ..self.styled_char(ELLIPSIS, offset, span) });
+        self.shape_item(&mut ellipsis);
+        let mut truncated = trim_end(chars).to_vec();
+        loop {
+            let mut candidate = truncated.clone();
+            candidate.extend(ellipsis.iter().cloned());
+            if truncated.is_empty() || self.measure(&candidate) <= available_width {
+                return BrokenLine { ends_paragraph: true, ..self.broken_line(candidate) };
+            }
+            truncated.pop();
+            truncated = trim_end(&truncated).to_vec();
+        }
+    }
+
+    fn broken_line(&self, chars: Vec<StyledChar>) -> BrokenLine {
+        BrokenLine {
+            offset: chars.iter().map(|styled| styled.offset).min().unwrap_or_default(),
+            width: self.measure(&chars),
+            chars,
+            ends_paragraph: false
+        }
+    }
+}
+
+fn same_run(styled: &StyledChar, other: &StyledChar) -> bool {
+    (styled.span, styled.font, styled.level) == (other.span, other.font, other.level)
+}
+
+fn words(paragraph: &[StyledChar]) -> Vec<(Option<&StyledChar>, &[StyledChar])> {
+    let mut words = vec!();
+    let mut separator = None;
+    let mut start = 0;
+
+    for (index, styled) in paragraph.iter().enumerate() {
+        if styled.character == ' ' {
+            if index > start {
+                words.push((separator, &paragraph[start..index]));
+            }
+            separator = Some(styled);
+            start = index + 1;
+        }
+    }
+
+    if paragraph.len() > start {
+        words.push((separator, &paragraph[start..]));
+    }
+
+    words
+}
+
+fn trim_end(chars: &[StyledChar]) -> &[StyledChar] {
+    let end = chars
+        .iter()
+        .rposition(|styled| !styled.character.is_whitespace())
+        .map_or(0, |index| index + 1);
+    &chars[..end]
+}
+
+fn align_line(align: TextAlign, direction: Direction, extra: f32) -> f32 {
+    match (align, direction) {
+        (TextAlign::Center, _) => extra / 2.0,
+        (TextAlign::Start, Direction::LeftToRight) | (TextAlign::Justify, Direction::LeftToRight) | (TextAlign::End, Direction::RightToLeft) => 0.0,
+        (TextAlign::Start, Direction::RightToLeft) | (TextAlign::Justify, Direction::RightToLeft) | (TextAlign::End, Direction::LeftToRight) => extra
+    }
+}
@@ -0,0 +1,148 @@
+use std::mem;
+use legion::*;
+use zodiac::*;
+use crate::algorithms::*;
+use crate::constraints::*;
+use crate::measurement::*;
+use crate::resizing::*;
+use crate::store::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct WrapDefinition {
+    pub flow: Flow,
+    pub line_spacing: f32,
+    pub line_alignment: LineAlignment
+}
+
+impl WrapDefinition {
+    fn main_axis(&self) -> LayoutAxis {
+        match self.flow {
+            Flow::Horizontal => LayoutAxis::Horizontal,
+            Flow::Vertical => LayoutAxis::Vertical
+        }
+    }
+
+    fn cross_axis(&self) -> LayoutAxis {
+        match self.flow {
+            Flow::Horizontal => LayoutAxis::Vertical,
+            Flow::Vertical => LayoutAxis::Horizontal
+        }
+    }
+
+    fn line_offset(&self, available: f32, used: f32) -> f32 {
+        let free = (available - used).max(0.0);
+        match self.line_alignment {
+            LineAlignment::Start => 0.0,
+            LineAlignment::Centre => free / 2.0,
+            LineAlignment::End => free
+        }
+    }
+
+    fn place(&self, constraints: &LayoutConstraints, main_offset: f32, cross_offset: f32, main: f32, cross: f32) -> LayoutConstraints {
+        match self.flow {
+            Flow::Horizontal => LayoutConstraints {
+                left: constraints.left + main_offset,
+                top: constraints.top + cross_offset,
+                width: main,
+                height: cross
+            },
+            Flow::Vertical => LayoutConstraints {
+                left: constraints.left + cross_offset,
+                top: constraints.top + main_offset,
+                width: cross,
+                height: main
+            }
+        }
+    }
+}
+
+fn get_definition(store: &LayoutNodeStore, entity: &Entity) -> WrapDefinition {
+    store.get(entity).map(|node| node.wrap_definition).unwrap_or_default()
+}
+
+#[derive(Default, Debug)]
+struct WrapLine {
+    items: Vec<(Entity, f32)>,
+    main: f32,
+    cross: f32
+}
+
+fn break_into_lines(items: Vec<(Entity, f32, f32)>, available_main: f32) -> Vec<WrapLine> {
+    let mut lines = vec!();
+    let mut line = WrapLine::default();
+
+    for (entity, main, cross) in items {
+        let main = if main > 0.0 { main } else { available_main };
+        if !line.items.is_empty() && line.main + main > available_main {
+            lines.push(mem::take(&mut line));
+        }
+        line.items.push((entity, main));
+        line.main += main;
+        line.cross = line.cross.max(cross);
+    }
+
+    if !line.items.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+pub struct WrapLayout {
+}
+
+impl LayoutAlgorithm for WrapLayout {
+    fn measure(&self, maps: &MeasurementMaps, entity: &Entity, axis: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        let definition = get_definition(maps.store, entity);
+        if axis == definition.main_axis() {
+            sum_of_sizes(child_sizes)
+        } else {
+            max_of_sizes(child_sizes)
+        }
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let definition = get_definition(maps.store, entity);
+        let (available_main, available_cross) = match definition.flow {
+            Flow::Horizontal => (constraints.width, constraints.height),
+            Flow::Vertical => (constraints.height, constraints.width)
+        };
+
+        let items = maps
+            .children(entity)
+            .map(|child| (
+                child,
+                maps.minimum_size(&child, definition.main_axis()),
+                maps.minimum_size(&child, definition.cross_axis())))
+            .collect();
+
+        let lines = break_into_lines(items, available_main);
+
+        let mut children = vec!();
+        let mut cross_offset = 0.0;
+
+        for line in &lines {
+            let mut main_offset = definition.line_offset(available_main, line.main);
+            for (child, main) in &line.items {
+                children.push((*child, definition.place(constraints, main_offset, cross_offset, *main, line.cross)));
+                main_offset += main;
+            }
+            cross_offset += line.cross + definition.line_spacing;
+        }
+
+        let overflow_main = lines
+            .iter()
+            .map(|line| line.main - available_main)
+            .fold(0.0, f32::max);
+        let used_cross = (cross_offset - definition.line_spacing).max(0.0);
+        let overflow_cross = (used_cross - available_cross).max(0.0);
+
+        let diagnostics = match definition.flow {
+            Flow::Horizontal => LayoutDiagnostics { overflow_width: overflow_main, overflow_height: overflow_cross },
+            Flow::Vertical => LayoutDiagnostics { overflow_width: overflow_cross, overflow_height: overflow_main }
+        };
+
+        Arrangement::new(children).with_diagnostics(diagnostics)
+    }
+}
@@ -76,8 +76,11 @@ void main()
 
     if(fs_in.identification.r == 2) 
     {
-        current_colour = vec3(1.0, 1.0, 1.0);
-        alpha = 1.0;
+        vec2 atlas_coord = mix(fs_in.extra_data_1.xy, fs_in.extra_data_1.zw, fs_in.texture_coord);
+        float dist = texture(font_buffer, vec3(atlas_coord, float(fs_in.identification.g))).r;
+        float edge_width = max(fwidth(dist), 0.01);
+        alpha = smoothstep(0.5 - edge_width, 0.5 + edge_width, dist) * fs_in.inner_colour.a;
+        current_colour = inner_colour;
     }
 
     Color = vec4(current_colour, alpha);
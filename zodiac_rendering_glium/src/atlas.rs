@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use zodiac::*;
+use crate::primitives::*;
+
+pub const GLYPH_ATLAS_PAGE_SIZE: u16 = 512;
+pub const GLYPH_SDF_FONT_SIZE: f32 = 32.0;
+pub const GLYPH_SDF_SPREAD: usize = 4;
+const GLYPH_ATLAS_GUTTER: u16 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasAllocation {
+    pub layer: u16,
+    pub left: u16,
+    pub top: u16
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasPacker {
+    page_size: u16,
+    layer: u16,
+    left: u16,
+    top: u16,
+    shelf_height: u16
+}
+
+impl AtlasPacker {
+    pub fn new(page_size: u16) -> Self {
+        Self {
+            page_size,
+            layer: 0,
+            left: 0,
+            top: 0,
+            shelf_height: 0
+        }
+    }
+
+    pub fn pack(&mut self, width: u16, height: u16) -> Option<AtlasAllocation> {
+        if width > self.page_size || height > self.page_size {
+            return None;
+        }
+
+        if self.left + width > self.page_size {
+            self.top += self.shelf_height;
+            self.left = 0;
+            self.shelf_height = 0;
+        }
+
+        if self.top + height > self.page_size {
+            self.layer += 1;
+            self.top = 0;
+            self.left = 0;
+            self.shelf_height = 0;
+        }
+
+        let allocation = AtlasAllocation {
+            layer: self.layer,
+            left: self.left,
+            top: self.top
+        };
+
+        self.left += width;
+        self.shelf_height = self.shelf_height.max(height);
+
+        Some(allocation)
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.layer as usize + 1
+    }
+}
+
+pub fn signed_distance_field(coverage: &GlyphCoverage, spread: usize) -> Vec<u8> {
+    let width = coverage.width + spread * 2;
+    let height = coverage.height + spread * 2;
+    let inside = |x: isize, y: isize| {
+        let x = x - spread as isize;
+        let y = y - spread as isize;
+        x >= 0 && y >= 0 && (x as usize) < coverage.width && (y as usize) < coverage.height
+            && coverage.coverage[y as usize * coverage.width + x as usize] >= 0.5
+    };
+    let radius = spread as isize;
+    let mut field = Vec::with_capacity(width * height);
+
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let is_inside = inside(x, y);
+            let mut nearest = (spread as f32 + 0.5) * (spread as f32 + 0.5);
+            for offset_y in -radius..=radius {
+                for offset_x in -radius..=radius {
+                    if inside(x + offset_x, y + offset_y) != is_inside {
+                        nearest = nearest.min((offset_x * offset_x + offset_y * offset_y) as f32);
+                    }
+                }
+            }
+            let distance = nearest.sqrt() - 0.5;
+            let signed_distance = if is_inside { distance } else { -distance };
+            let value = 0.5 + signed_distance / (spread as f32 * 2.0);
+            field.push((value.max(0.0).min(1.0) * 255.0).round() as u8);
+        }
+    }
+
+    field
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasGlyph {
+    pub layer: u16,
+    pub uv: [f32; 4],
+    pub offset: [f32; 2],
+    pub size: [f32; 2]
+}
+
+pub fn create_glyph_atlas() -> GlyphAtlas {
+    GlyphAtlas::new(GLYPH_ATLAS_PAGE_SIZE)
+}
+
+pub struct GlyphAtlas {
+    packer: AtlasPacker,
+    pages: Vec<Vec<u8>>,
+    glyphs: HashMap<(FontId, u16), Option<AtlasGlyph>>,
+    changed: bool
+}
+
+impl GlyphAtlas {
+    pub fn new(page_size: u16) -> Self {
+        Self {
+            packer: AtlasPacker::new(page_size),
+            pages: vec!(vec!(0; page_size as usize * page_size as usize)),
+            glyphs: HashMap::default(),
+            changed: true
+        }
+    }
+
+    pub fn page_size(&self) -> u16 {
+        self.packer.page_size
+    }
+
+    pub fn pages(&self) -> &[Vec<u8>] {
+        &self.pages
+    }
+
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.changed, false)
+    }
+
+    pub fn glyph(&mut self, fonts: &FontRegistry, font: FontId, id: u16) -> Option<AtlasGlyph> {
+        if let Some(glyph) = self.glyphs.get(&(font, id)) {
+            return *glyph;
+        }
+
+        let glyph = fonts
+            .face(font)
+            .rasterise_glyph(id, GLYPH_SDF_FONT_SIZE)
+            .and_then(|coverage| self.add(&coverage));
+
+        self.glyphs.insert((font, id), glyph);
+        glyph
+    }
+
+    fn add(&mut self, coverage: &GlyphCoverage) -> Option<AtlasGlyph> {
+        let width = coverage.width + GLYPH_SDF_SPREAD * 2;
+        let height = coverage.height + GLYPH_SDF_SPREAD * 2;
+        let allocation = self.packer.pack(width as u16 + GLYPH_ATLAS_GUTTER, height as u16 + GLYPH_ATLAS_GUTTER)?;
+        let page_size = self.page_size() as usize;
+
+        while self.pages.len() < self.packer.page_count() {
+            self.pages.push(vec!(0; page_size * page_size));
+        }
+
+        let field = signed_distance_field(coverage, GLYPH_SDF_SPREAD);
+        let page = &mut self.pages[allocation.layer as usize];
+        for (row, values) in field.chunks(width).enumerate() {
+            let start = (allocation.top as usize + row) * page_size + allocation.left as usize;
+            page[start..start + width].copy_from_slice(values);
+        }
+        self.changed = true;
+
+        let page_size = page_size as f32;
+        Some(AtlasGlyph {
+            layer: allocation.layer,
+            uv: [
+                allocation.left as f32 / page_size,
+                allocation.top as f32 / page_size,
+                (allocation.left as usize + width) as f32 / page_size,
+                (allocation.top as usize + height) as f32 / page_size
+            ],
+            offset: [
+                coverage.left - GLYPH_SDF_SPREAD as f32,
+                coverage.top - GLYPH_SDF_SPREAD as f32
+            ],
+            size: [width as f32, height as f32]
+        })
+    }
+}
+
+fn raster_rectangle(left: f32, top: f32, width: f32, height: f32, scale_factor: f32) -> ([u16; 2], [u16; 2]) {
+    let position = [rasterise(left, scale_factor), rasterise(top, scale_factor)];
+    (
+        position,
+        [
+            rasterise(left + width, scale_factor).saturating_sub(position[0]),
+            rasterise(top + height, scale_factor).saturating_sub(position[1])
+        ]
+    )
+}
+
+pub fn text_primitives(
+    atlas: &mut GlyphAtlas,
+    fonts: &FontRegistry,
+    text_lines: &TextLines,
+    origin: [f32; 2],
+    colour: Colour,
+    scale_factor: f32) -> Vec<RenderPrimitive> {
+        let mut primitives = Vec::default();
+
+        for run in &text_lines.runs {
+            let left = origin[0] + run.left;
+            let top = origin[1] + run.top;
+            let baseline = top + text_lines.baseline;
+            let run_colour: [f32; 4] = run.colour.unwrap_or(colour).into();
+
+            if let Some(background) = run.background {
+                let (position, dimensions) = raster_rectangle(left, top, run.width, text_lines.line_height, scale_factor);
+                primitives.push(RenderPrimitive::rectangle(position, dimensions, background.into(), background.into(), 0.0, [0, 0, 0, 0]));
+            }
+
+            let scale = run.font_size / GLYPH_SDF_FONT_SIZE;
+            for glyph in &run.glyphs {
+                if let Some(atlas_glyph) = atlas.glyph(fonts, run.font, glyph.id) {
+                    let (position, dimensions) = raster_rectangle(
+                        left + glyph.left + atlas_glyph.offset[0] * scale,
+                        baseline + glyph.top + atlas_glyph.offset[1] * scale,
+                        atlas_glyph.size[0] * scale,
+                        atlas_glyph.size[1] * scale,
+                        scale_factor);
+                    primitives.push(RenderPrimitive::glyph(position, dimensions, run_colour, atlas_glyph.layer, atlas_glyph.uv));
+                }
+            }
+
+            if run.underline {
+                let thickness = (run.font_size / 16.0).max(1.0);
+                let (position, dimensions) = raster_rectangle(left, baseline + thickness, run.width, thickness, scale_factor);
+                primitives.push(RenderPrimitive::rectangle(position, dimensions, run_colour, run_colour, 0.0, [0, 0, 0, 0]));
+            }
+        }
+
+        primitives
+}
+
+fn clip_primitive(primitive: RenderPrimitive, position: [u16; 2], dimensions: [u16; 2]) -> Option<RenderPrimitive> {
+    let left = primitive.position[0].max(position[0]);
+    let top = primitive.position[1].max(position[1]);
+    let right = primitive.position[0].saturating_add(primitive.dimensions[0]).min(position[0].saturating_add(dimensions[0]));
+    let bottom = primitive.position[1].saturating_add(primitive.dimensions[1]).min(position[1].saturating_add(dimensions[1]));
+    if right <= left || bottom <= top {
+        return None;
+    }
+
+    let mut clipped = primitive;
+    clipped.position = [left, top];
+    clipped.dimensions = [right - left, bottom - top];
+    if primitive.identification[0] == 2 {
+        let uv = primitive.extra_data_1;
+        let interpolate = |from: f32, to: f32, offset: u16, start: u16, length: u16| from + (to - from) * (offset - start) as f32 / length as f32;
+        clipped.extra_data_1 = [
+            interpolate(uv[0], uv[2], left, primitive.position[0], primitive.dimensions[0]),
+            interpolate(uv[1], uv[3], top, primitive.position[1], primitive.dimensions[1]),
+            interpolate(uv[0], uv[2], right, primitive.position[0], primitive.dimensions[0]),
+            interpolate(uv[1], uv[3], bottom, primitive.position[1], primitive.dimensions[1])
+        ];
+    }
+    Some(clipped)
+}
+
+pub fn text_input_primitives(
+    atlas: &mut GlyphAtlas,
+    fonts: &FontRegistry,
+    text_lines: &TextLines,
+    text_edit_state: &TextEditState,
+    layout_change: &LayoutChange,
+    colour: Colour,
+    scale_factor: f32) -> Vec<RenderPrimitive> {
+        let geometry = text_edit_geometry(text_lines, text_edit_state, layout_change.left, layout_change.top);
+        let text_colour = if text_edit_state.is_empty() { placeholder_colour(colour) } else { colour };
+
+        let mut primitives = selection_primitives(&geometry.selections, colour, scale_factor);
+        primitives.extend(text_primitives(
+            atlas,
+            fonts,
+            text_lines,
+            [layout_change.left - text_edit_state.scroll_left, layout_change.top - text_edit_state.scroll_top],
+            text_colour,
+            scale_factor));
+        primitives.extend(geometry.caret.map(|caret| rect_primitive(&caret, colour.into(), scale_factor)));
+
+        let position = layout_change.raster_position(scale_factor);
+        let dimensions = layout_change.raster_dimensions(scale_factor);
+        primitives
+            .into_iter()
+            .filter_map(|primitive| clip_primitive(primitive, position, dimensions))
+            .collect()
+}
+
+pub fn selection_primitives(selections: &[TextRect], colour: Colour, scale_factor: f32) -> Vec<RenderPrimitive> {
+    let colour: [f32; 4] = selection_colour(colour).into();
+    selections
+        .iter()
+        .map(|selection| rect_primitive(selection, colour, scale_factor))
+        .collect()
+}
+
+fn rect_primitive(rect: &TextRect, colour: [f32; 4], scale_factor: f32) -> RenderPrimitive {
+    RenderPrimitive::rectangle(
+        rect.raster_position(scale_factor),
+        rect.raster_dimensions(scale_factor),
+        colour,
+        colour,
+        0.0,
+        [0, 0, 0, 0])
+}
@@ -10,10 +10,22 @@ element! {
     attributes {
         left(u16)
         top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
         radius(u16)
         colour((u8, u8, u8, u8))
         stroke_colour((u8, u8, u8, u8))
         stroke_width(u16)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
     }
 }
 
@@ -26,11 +38,155 @@ element! {
     attributes {
         left(u16)
         top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
         width(u16)
         height(u16)
+        aspect_ratio((u16, u16))
         colour((u8, u8, u8, u8))
         stroke_colour((u8, u8, u8, u8))
         stroke_width(u16)
         corner_radii((u16, u16, u16, u16))
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <text>
+    [Text::default()]
+    extra_components {
+        [Renderable::default()]
+    }
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        colour((u8, u8, u8, u8))
+        content(String)
+        font_size(u8)
+        font_family(String)
+        font_weight(u16)
+        font_style(FontStyle)
+        text_wrap(TextWrap)
+        text_align(TextAlign)
+        line_height(u16)
+        max_lines(u16)
+        selectable(bool)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <rich_text>
+    [RichText::default()]
+    extra_components {
+        [Renderable::default()]
+    }
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        colour((u8, u8, u8, u8))
+        font_size(u8)
+        font_family(String)
+        font_weight(u16)
+        font_style(FontStyle)
+        text_wrap(TextWrap)
+        text_align(TextAlign)
+        line_height(u16)
+        max_lines(u16)
+        selectable(bool)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
+    }
+}
+
+element! {
+    <span>
+    [Span::default()]
+    attributes {
+        content(String)
+        colour((u8, u8, u8, u8))
+        font_size(u8)
+        font_family(String)
+        font_weight(u16)
+        font_style(FontStyle)
+        underline(bool)
+        background((u8, u8, u8, u8))
+    }
+}
+
+element! {
+    <text_input>
+    [TextInput::default()]
+    extra_components {
+        [Renderable::default()]
+        [Editable::default()]
+    }
+    attributes {
+        left(u16)
+        top(u16)
+        right(u16)
+        bottom(u16)
+        center_x(i16)
+        center_y(i16)
+        width(u16)
+        height(u16)
+        aspect_ratio((u16, u16))
+        colour((u8, u8, u8, u8))
+        value(String)
+        placeholder(String)
+        multi_line(bool)
+        on_change(TextHandler)
+        on_submit(TextHandler)
+        font_size(u8)
+        font_family(String)
+        font_weight(u16)
+        font_style(FontStyle)
+        text_wrap(TextWrap)
+        text_align(TextAlign)
+        line_height(u16)
+        row(u16)
+        column(u16)
+        row_span(u16)
+        column_span(u16)
+        dock(Dock)
+        alignment(Alignment)
+        direction(Direction)
+        visibility(Visibility)
     }
 }
\ No newline at end of file
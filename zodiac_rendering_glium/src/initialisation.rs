@@ -31,8 +31,13 @@ impl ApplicationBundleBuilder for GliumRendererBuilder {
 
     fn setup_rendering_systems(&self, builder: &mut Builder) {
         builder
+            .add_thread_local(remove_invisible_primitives_system())
+            .add_thread_local(remove_invisible_text_primitives_system())
             .add_thread_local(queue_render_rectangle_primitives_system())
             .add_thread_local(queue_render_circle_primitives_system())
+            .add_thread_local(queue_render_layout_outline_primitives_system())
+            .add_thread_local(queue_render_text_primitives_system())
+            .add_thread_local(queue_render_text_input_primitives_system())
             .add_thread_local(render_primitives_system());
     }
 
@@ -44,17 +49,26 @@ impl ApplicationBundleBuilder for GliumRendererBuilder {
 
     fn setup_resources(&self, resources: &mut Resources, _: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
         let mut event_loop = create_system_event_loop();
+        register_fonts(resources, |_| Ok(()))?;
         
         resources.insert(create_glium_renderer(&mut event_loop)?);
         resources.insert(event_loop);
+        resources.insert(create_input_tracker());
+        register_system_clipboard(resources);
         resources.insert(create_glium_render_queue());
+        resources.insert(create_glyph_atlas());
         
         Ok(())
     }
 
     fn register_components_for_world_serializiation(&self, world_serializer: &mut WorldSerializer) {
         world_serializer.register_component::<RenderPrimitive>(stringify!(RenderPrimitive));
+        world_serializer.register_component::<TextPrimitives>(stringify!(TextPrimitives));
         world_serializer.register_component::<Circle>(stringify!(Circle));
         world_serializer.register_component::<Rectangle>(stringify!(Rectangle));
+        world_serializer.register_component::<Text>(stringify!(Text));
+        world_serializer.register_component::<RichText>(stringify!(RichText));
+        world_serializer.register_component::<Span>(stringify!(Span));
+        world_serializer.register_component::<TextInput>(stringify!(TextInput));
     }
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ pub mod display;
 pub mod rendering;
 pub mod render_queue;
 pub mod primitives;
+pub mod atlas;
 pub mod matrices;
 pub mod events;
 pub mod systems;
@@ -17,5 +18,7 @@ pub use systems::render_primitives::*;
 pub use systems::events::*;
 pub use systems::rendering::*;
 pub use primitives::RenderPrimitive;
+pub use primitives::TextPrimitives;
+pub use atlas::*;
 pub use events::*;
 pub use initialisation::*;
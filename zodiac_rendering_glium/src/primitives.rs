@@ -78,19 +78,25 @@ impl RenderPrimitive {
         ) 
     }
 
-    pub fn text(
+    pub fn glyph(
         position: [u16; 2],
         dimensions: [u16; 2],
         colour: [f32; 4],
-        _: String) -> Self {
+        layer: u16,
+        uv: [f32; 4]) -> Self {
         RenderPrimitive::with_position_size_colours_identification_and_data(
             position,
             dimensions,
             colour,
             colour,
-            [2, 0],
-            [0.0, 0.0, 0.0, 0.0],
+            [2, layer],
+            uv,
             [0, 0, 0, 0]
         ) 
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TextPrimitives {
+    pub primitives: Vec<RenderPrimitive>
+}
@@ -62,4 +62,13 @@ impl GliumRenderQueue {
                 outer_colour.into(),
                 stroke_width as f32));
     }
+
+    pub fn queue_text_for_render(
+        &mut self,
+        command_buffer: &mut CommandBuffer,
+        entity: &Entity,
+        primitives: Vec<RenderPrimitive>) {
+        debug!("Queueing text: {:?}", primitives.len());
+        command_buffer.add_component(*entity, TextPrimitives { primitives })
+    }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use log::{debug};
+use std::borrow::Cow;
 
 use glium::*;
 use glium::index::*;
+use glium::texture::{ ClientFormat, MipmapsOption, RawImage2d, Texture2dArray, UncompressedFloatFormat };
+use glium::uniforms::{ MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction };
 use glium::glutin::event_loop::*;
 use zodiac::*;
 
@@ -16,15 +19,31 @@ pub fn create_glium_renderer(event_loop: &EventLoop<()>) -> Result<GliumRenderer
 pub struct GliumRenderer {
     display: Display,
     shader_program: Program,
-    vertex_buffer: VertexBuffer::<RenderPrimitive>
+    vertex_buffer: VertexBuffer::<RenderPrimitive>,
+    font_texture: Texture2dArray
 }
 
 impl Renderer for GliumRenderer {
     fn get_window_dimensions(&self) -> Dimensions {
         Dimensions::from(self.display.get_framebuffer_dimensions())
+            .with_scale_factor(self.display.gl_window().window().scale_factor() as f32)
     }
 }
 
+fn create_font_texture(display: &Display, page_size: u16, pages: &[Vec<u8>]) -> Result<Texture2dArray, RendererError> {
+    let images = pages
+        .iter()
+        .map(|page| RawImage2d {
+            data: Cow::Borrowed(&page[..]),
+            width: page_size as u32,
+            height: page_size as u32,
+            format: ClientFormat::U8
+        })
+        .collect();
+    Texture2dArray::with_format(display, images, UncompressedFloatFormat::U8, MipmapsOption::NoMipmap)
+        .map_err(|_|RendererError::TextureCreationError)
+}
+
 fn get_shader_error_message(from: ProgramCreationError) -> String {
     match from {
         ProgramCreationError::CompilationError(message, _) => message,
@@ -38,13 +57,20 @@ impl GliumRenderer {
         let display = create_display(event_loop).map_err(|_|RendererError::FailedToDisplayWindow)?;
         let shader_program = create_shader_program(&display).map_err(|e|RendererError::FailedToCreateShaders(get_shader_error_message(e)))?;
         let vertex_buffer = VertexBuffer::<RenderPrimitive>::empty_dynamic(&display, 0).map_err(|_|RendererError::BufferCreationError)?;
+        let font_texture = create_font_texture(&display, 1, &[vec!(0)])?;
         Ok(Self {
             display,
             shader_program,
-            vertex_buffer
+            vertex_buffer,
+            font_texture
         })
     }
 
+    pub fn set_font_pages(&mut self, page_size: u16, pages: &[Vec<u8>]) -> Result<(), RendererError> {
+        self.font_texture = create_font_texture(&self.display, page_size, pages)?;
+        Ok(())
+    }
+
     pub fn set_primitives(&mut self, to_set: &Vec::<RenderPrimitive>) -> Result<(), RendererError>  {
         self.vertex_buffer = VertexBuffer::<RenderPrimitive>::dynamic(&self.display, to_set).map_err(|_|RendererError::BufferCreationError)?;
         Ok(())
@@ -58,11 +84,6 @@ impl GliumRenderer {
 
         let params = glium::DrawParameters {
             blend: glium::Blend::alpha_blending(),
-            depth: glium::Depth {
-                test: glium::draw_parameters::DepthTest::IfLess,
-                write: true,
-                ..Default::default()
-            },
             .. Default::default()
         };
 
@@ -72,7 +93,12 @@ impl GliumRenderer {
         //debug!("width: {}, height: {}", width, height);
 
         let uniforms = uniform! {
-            uResolution: [width as f32, height as f32]
+            uResolution: [width as f32, height as f32],
+            font_buffer: self.font_texture
+                .sampled()
+                .minify_filter(MinifySamplerFilter::Linear)
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .wrap_function(SamplerWrapFunction::Clamp)
         };
 
         target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
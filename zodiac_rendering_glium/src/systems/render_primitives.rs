@@ -4,15 +4,18 @@ use legion::world::*;
 use zodiac::*;
 
 use crate::GliumRenderer;
-use crate::RenderPrimitive;
+use crate::GlyphAtlas;
+use crate::primitives::*;
 
 #[system(simple)]
 #[read_component(Renderable)]
 #[read_component(LayoutChange)]
 #[read_component(Rebuild)]
 #[read_component(RenderPrimitive)]
+#[read_component(TextPrimitives)]
 pub fn render_primitives(
     world: &mut SubWorld,
+    #[resource] atlas: &mut GlyphAtlas,
     #[resource] renderer: &mut GliumRenderer) {
     
     if <(&Renderable, &LayoutChange)>::query().iter(world).count() == 0 {
@@ -21,10 +24,18 @@ pub fn render_primitives(
         }
     }
 
-    let primitives: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+    let mut primitives: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
         .iter(world)
         .map(|primitive| *primitive)
         .collect();
+
+    primitives.extend(<&TextPrimitives>::query()
+        .iter(world)
+        .flat_map(|text| text.primitives.iter().copied()));
+
+    if atlas.take_changed() {
+        renderer.set_font_pages(atlas.page_size(), atlas.pages()).unwrap();
+    }
        
     renderer.set_primitives(&primitives).unwrap();
     
@@ -2,10 +2,13 @@ use legion::*;
 use legion::systems::*;
 use zodiac::*;
 use crate::GliumRenderQueue;
+use crate::GliumRenderer;
 use crate::components::*;
+use crate::primitives::*;
+use crate::atlas::*;
 
 #[system(for_each)]
-#[filter(component::<Rectangle>())]
+#[filter(component::<Rectangle>() & !component::<Invisible>())]
 pub fn queue_render_rectangle_primitives(
     entity: &Entity, 
     layout_change: &LayoutChange, 
@@ -14,20 +17,27 @@ pub fn queue_render_rectangle_primitives(
     stroke_width: &StrokeWidth,
     corner_radii: &CornerRadii,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut GliumRenderQueue) {
+    #[resource] render_queue: &mut GliumRenderQueue,
+    #[resource] renderer: &GliumRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_rectangle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        [layout_change.width, layout_change.height],
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
         *colour,
         *stroke_colour, 
-        stroke_width.width, 
-        [corner_radii.left_top, corner_radii.right_top, corner_radii.right_bottom, corner_radii.left_bottom]);
+        rasterise(stroke_width.width as f32, scale_factor), 
+        [
+            rasterise(corner_radii.left_top as f32, scale_factor),
+            rasterise(corner_radii.right_top as f32, scale_factor),
+            rasterise(corner_radii.right_bottom as f32, scale_factor),
+            rasterise(corner_radii.left_bottom as f32, scale_factor)
+        ]);
 }
 
 #[system(for_each)]
-#[filter(component::<Circle>())]
+#[filter(component::<Circle>() & !component::<Invisible>())]
 pub fn queue_render_circle_primitives(
     entity: &Entity,
     layout_change: &LayoutChange, 
@@ -35,13 +45,110 @@ pub fn queue_render_circle_primitives(
     stroke_colour: &StrokeColour,
     stroke_width: &StrokeWidth,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut GliumRenderQueue) {
+    #[resource] render_queue: &mut GliumRenderQueue,
+    #[resource] renderer: &GliumRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_circle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        layout_change.width,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor)[0],
         *colour,
         *stroke_colour, 
-        stroke_width.width);
-}
\ No newline at end of file
+        rasterise(stroke_width.width as f32, scale_factor));
+}
+
+#[system(for_each)]
+#[filter((component::<Text>() | component::<RichText>()) & !component::<Invisible>())]
+pub fn queue_render_text_primitives(
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    colour: &Colour,
+    text_lines: &TextLines,
+    selectable: Option<&Selectable>,
+    text_selection: Option<&TextSelection>,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut GliumRenderQueue,
+    #[resource] atlas: &mut GlyphAtlas,
+    #[resource] fonts: &FontRegistry,
+    #[resource] renderer: &GliumRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    let mut primitives = match (selectable, text_selection) {
+        (Some(selectable), Some(text_selection)) if selectable.selectable => {
+            let (start, end) = text_selection.selection();
+            selection_primitives(
+                &selection_rects(text_lines, start, end, layout_change.left, layout_change.top),
+                *colour,
+                scale_factor)
+        },
+        _ => vec!()
+    };
+    primitives.extend(text_primitives(
+        atlas,
+        fonts,
+        text_lines,
+        [layout_change.left, layout_change.top],
+        *colour,
+        scale_factor));
+    render_queue.queue_text_for_render(command_buffer, entity, primitives);
+}
+
+#[system(for_each)]
+#[filter(component::<TextInput>() & !component::<Invisible>())]
+pub fn queue_render_text_input_primitives(
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    colour: &Colour,
+    text_lines: &TextLines,
+    text_edit_state: &TextEditState,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut GliumRenderQueue,
+    #[resource] atlas: &mut GlyphAtlas,
+    #[resource] fonts: &FontRegistry,
+    #[resource] renderer: &GliumRenderer) {
+    render_queue.queue_text_for_render(
+        command_buffer,
+        entity,
+        text_input_primitives(
+            atlas,
+            fonts,
+            text_lines,
+            text_edit_state,
+            layout_change,
+            *colour,
+            renderer.get_scale_factor()));
+}
+
+#[system(for_each)]
+#[filter(component::<LayoutOutline>())]
+pub fn queue_render_layout_outline_primitives(
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    stroke_colour: &StrokeColour,
+    stroke_width: &StrokeWidth,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut GliumRenderQueue,
+    #[resource] renderer: &GliumRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    render_queue.queue_rectangle_for_render(
+        command_buffer,
+        entity,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        Colour::from((0, 0, 0, 0)),
+        *stroke_colour,
+        rasterise(stroke_width.width as f32, scale_factor),
+        [0, 0, 0, 0]);
+}
+
+#[system(for_each)]
+#[filter(component::<Invisible>() & component::<RenderPrimitive>())]
+pub fn remove_invisible_primitives(entity: &Entity, command_buffer: &mut CommandBuffer) {
+    command_buffer.remove_component::<RenderPrimitive>(*entity);
+}
+
+#[system(for_each)]
+#[filter(component::<Invisible>() & component::<TextPrimitives>())]
+pub fn remove_invisible_text_primitives(entity: &Entity, command_buffer: &mut CommandBuffer) {
+    command_buffer.remove_component::<TextPrimitives>(*entity);
+}
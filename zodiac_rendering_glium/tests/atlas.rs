@@ -0,0 +1,176 @@
+use zodiac::*;
+use zodiac_rendering_glium::*;
+
+fn glyph_id(fonts: &FontRegistry, character: char) -> u16 {
+    fonts.face(FontId::default()).glyph_id(character)
+}
+
+fn text_lines(fonts: &FontRegistry, text: &str) -> TextLines {
+    let mut left = 0.0;
+    let glyphs = text
+        .char_indices()
+        .map(|(cluster, character)| {
+            let advance = fonts.face(FontId::default()).advance(character, 16.0);
+            let glyph = TextGlyph {
+                id: glyph_id(fonts, character),
+                cluster,
+                left,
+                top: 0.0,
+                advance
+            };
+            left += advance;
+            glyph
+        })
+        .collect();
+
+    TextLines {
+        runs: vec!(TextRun {
+            text: text.to_string(),
+            glyphs,
+            font_size: 16.0,
+            left: 4.0,
+            top: 2.0,
+            width: left,
+            ..TextRun::default()
+        }),
+        line_height: 18.625,
+        baseline: 14.8515625
+    }
+}
+
+#[test]
+fn packer_places_allocations_along_a_shelf() {
+    let mut packer = AtlasPacker::new(32);
+
+    assert_eq!(packer.pack(10, 20), Some(AtlasAllocation { layer: 0, left: 0, top: 0 }));
+    assert_eq!(packer.pack(10, 5), Some(AtlasAllocation { layer: 0, left: 10, top: 0 }));
+}
+
+#[test]
+fn packer_starts_a_new_shelf_when_a_row_is_full() {
+    let mut packer = AtlasPacker::new(32);
+    packer.pack(20, 10);
+    packer.pack(8, 12);
+
+    assert_eq!(packer.pack(20, 8), Some(AtlasAllocation { layer: 0, left: 0, top: 12 }));
+}
+
+#[test]
+fn packer_starts_a_new_layer_when_a_page_is_full() {
+    let mut packer = AtlasPacker::new(32);
+    packer.pack(32, 20);
+
+    assert_eq!(packer.pack(32, 20), Some(AtlasAllocation { layer: 1, left: 0, top: 0 }));
+    assert_eq!(packer.page_count(), 2);
+}
+
+#[test]
+fn packer_rejects_allocations_larger_than_a_page() {
+    let mut packer = AtlasPacker::new(32);
+
+    assert_eq!(packer.pack(33, 10), None);
+    assert_eq!(packer.pack(10, 33), None);
+}
+
+#[test]
+fn signed_distance_field_pads_coverage_by_spread() {
+    let coverage = GlyphCoverage {
+        width: 2,
+        height: 2,
+        coverage: vec!(1.0; 4),
+        ..GlyphCoverage::default()
+    };
+
+    assert_eq!(signed_distance_field(&coverage, 2), vec!(
+        0, 17, 32, 32, 17, 0,
+        17, 69, 96, 96, 69, 17,
+        32, 96, 159, 159, 96, 32,
+        32, 96, 159, 159, 96, 32,
+        17, 69, 96, 96, 69, 17,
+        0, 17, 32, 32, 17, 0
+    ));
+}
+
+#[test]
+fn glyph_atlas_caches_rasterised_glyphs() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+    atlas.take_changed();
+
+    let first = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'a'));
+    assert_eq!(atlas.take_changed(), true);
+
+    let second = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'a'));
+    assert_eq!(atlas.take_changed(), false);
+    assert_eq!(first, second);
+    assert_eq!(first, Some(AtlasGlyph { layer: 0, uv: [0.0, 0.0, 0.375, 0.421875], offset: [-3.0, -22.0], size: [24.0, 27.0] }));
+}
+
+#[test]
+fn glyph_atlas_adds_pages_when_full() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+
+    for character in "abcdef".chars() {
+        atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, character));
+    }
+
+    assert_eq!(atlas.pages().len(), 3);
+    assert_eq!(atlas.pages().iter().all(|page| page.len() == 64 * 64), true);
+    assert_eq!(atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'f')).map(|glyph| glyph.layer), Some(2));
+}
+
+#[test]
+fn glyph_atlas_skips_glyphs_without_outlines() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+
+    assert_eq!(atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, ' ')), None);
+}
+
+#[test]
+fn text_primitives_emit_one_primitive_per_glyph() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+    let lines = text_lines(&fonts, "a b");
+
+    let primitives = text_primitives(&mut atlas, &fonts, &lines, [10.0, 20.0], Colour::from((255, 255, 255, 255)), 1.0);
+
+    let a = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'a')).unwrap();
+    let b = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'b')).unwrap();
+    assert_eq!(primitives, vec!(
+        RenderPrimitive::glyph([13, 26], [12, 13], [1.0, 1.0, 1.0, 1.0], a.layer, a.uv),
+        RenderPrimitive::glyph([28, 22], [12, 17], [1.0, 1.0, 1.0, 1.0], b.layer, b.uv)
+    ));
+}
+
+#[test]
+fn text_primitives_scale_glyph_quads() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+    let lines = text_lines(&fonts, "a");
+
+    let primitives = text_primitives(&mut atlas, &fonts, &lines, [10.0, 20.0], Colour::from((255, 255, 255, 255)), 2.0);
+
+    let a = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'a')).unwrap();
+    assert_eq!(primitives, vec!(RenderPrimitive::glyph([25, 52], [24, 27], [1.0, 1.0, 1.0, 1.0], a.layer, a.uv)));
+}
+
+#[test]
+fn text_primitives_draw_run_backgrounds_and_underlines() {
+    let fonts = create_font_registry().unwrap();
+    let mut atlas = GlyphAtlas::new(64);
+    let mut lines = text_lines(&fonts, "a");
+    lines.runs[0].colour = Some(Colour::from((255, 0, 0, 255)));
+    lines.runs[0].background = Some(Colour::from((0, 0, 255, 255)));
+    lines.runs[0].underline = true;
+
+    let primitives = text_primitives(&mut atlas, &fonts, &lines, [10.0, 20.0], Colour::from((255, 255, 255, 255)), 1.0);
+
+    let a = atlas.glyph(&fonts, FontId::default(), glyph_id(&fonts, 'a')).unwrap();
+    assert_eq!(primitives, vec!(
+        RenderPrimitive::rectangle([14, 22], [10, 19], [0.0, 0.0, 1.0, 1.0], [0.0, 0.0, 1.0, 1.0], 0.0, [0, 0, 0, 0]),
+        RenderPrimitive::glyph([13, 26], [12, 13], [1.0, 0.0, 0.0, 1.0], a.layer, a.uv),
+        RenderPrimitive::rectangle([14, 38], [10, 1], [1.0, 0.0, 0.0, 1.0], [1.0, 0.0, 0.0, 1.0], 0.0, [0, 0, 0, 0])
+    ));
+}
@@ -21,6 +21,7 @@ pub fn create_display(event_loop: &EventLoop<()>, physical_window_size: WrappedD
 pub struct Display {
     context: ContextWrapper<PossiblyCurrent, Window>,
     renderer: Renderer<GLDevice>,
+    font_context: CanvasFontContext,
     physical_window_size: WrappedDimensions
 }
 
@@ -53,6 +54,7 @@ impl Display {
         Display {
             context: gl_context,
             renderer,
+            font_context: CanvasFontContext::from_system_source(),
             physical_window_size
         }
     }
@@ -67,11 +69,9 @@ impl Display {
     pub fn render_canvas<F>(&mut self, render_func: F) 
         where F: Fn(&mut CanvasRenderingContext2D) -> () {
         
-        let font_context = CanvasFontContext::from_system_source();
-
         let mut canvas = Canvas
             ::new(Vector2F::from(self.physical_window_size))
-            .get_context_2d(font_context);
+            .get_context_2d(self.font_context.clone());
 
         render_func(&mut canvas);
         
@@ -84,6 +84,6 @@ impl Display {
     }
 
     pub fn get_window_dimensions(&self) -> WrappedDimensions {
-        WrappedDimensions::from(self.context.window().inner_size())
+        WrappedDimensions::from((self.context.window().inner_size(), self.context.window().scale_factor()))
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use log::*;
 use glutin::{dpi::PhysicalSize, event_loop::*};
 use pathfinder_canvas::*;
 use shrev::*;
+use winit::event::{ WindowEvent, ElementState, MouseButton, KeyboardInput, ModifiersState, VirtualKeyCode };
 use zodiac::*;
 
 pub fn create_pathfinder_event_reader_registry(event_channel: &mut EventChannel::<SystemEvent>) -> PathFinderEventReaderRegistry {
@@ -25,7 +26,13 @@ pub struct WrappedDimensions(Dimensions);
 
 impl From<PhysicalSize<u32>> for WrappedDimensions {
     fn from(size: PhysicalSize<u32>) -> Self {
-        Self(Dimensions { width: size.width as u16, height: size.height as u16 })
+        Self(Dimensions::new(size.width as u16, size.height as u16))
+    }
+}
+
+impl From<(PhysicalSize<u32>, f64)> for WrappedDimensions {
+    fn from((size, scale_factor): (PhysicalSize<u32>, f64)) -> Self {
+        Self(Dimensions::new(size.width as u16, size.height as u16).with_scale_factor(scale_factor as f32))
     }
 }
 
@@ -88,3 +95,115 @@ impl Into<SystemEvent> for WrappedSystemEvent {
 pub fn create_system_event_loop() -> EventLoop<()> {
     EventLoop::new()
 }
+
+pub fn create_input_tracker() -> InputTracker {
+    InputTracker::default()
+}
+
+#[derive(Default, Debug)]
+pub struct InputTracker {
+    modifiers: KeyModifiers,
+    pointer: (f32, f32),
+    received_text: String
+}
+
+impl InputTracker {
+    pub fn translate(&mut self, event: &WindowEvent, scale_factor: f64) -> Option<SystemEvent> {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.pointer = ((position.x / scale_factor) as f32, (position.y / scale_factor) as f32);
+                Some(SystemEvent::Input(SystemInputEventType::PointerMoved(self.pointer.0, self.pointer.1, self.modifiers)))
+            },
+            #[allow(deprecated)]
+            WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, modifiers, .. } => {
+                self.modifiers = key_modifiers(*modifiers);
+                Some(SystemEvent::Input(SystemInputEventType::PointerPressed(self.pointer.0, self.pointer.1, self.modifiers)))
+            },
+            #[allow(deprecated)]
+            WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, modifiers, .. } => {
+                self.modifiers = key_modifiers(*modifiers);
+                Some(SystemEvent::Input(SystemInputEventType::PointerReleased(self.pointer.0, self.pointer.1, self.modifiers)))
+            },
+            #[allow(deprecated)]
+            WindowEvent::KeyboardInput { input: KeyboardInput { state, virtual_keycode, modifiers, .. }, .. } => {
+                self.modifiers = key_modifiers(*modifiers);
+                match (state, virtual_keycode) {
+                    (ElementState::Pressed, Some(keycode)) =>
+                        key_code(*keycode).map(|key| SystemEvent::Input(SystemInputEventType::KeyPressed(key, self.modifiers))),
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    pub fn receive_text(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::ReceivedCharacter(character) => {
+                self.received_text.push(*character);
+                true
+            },
+            _ => false
+        }
+    }
+
+    pub fn take_received_text(&mut self) -> Option<SystemEvent> {
+        if self.received_text.is_empty() {
+            return None;
+        }
+        Some(SystemEvent::Input(SystemInputEventType::TextReceived(std::mem::take(&mut self.received_text))))
+    }
+}
+
+fn key_modifiers(modifiers: ModifiersState) -> KeyModifiers {
+    KeyModifiers {
+        shift: modifiers.shift(),
+        control: modifiers.ctrl(),
+        alt: modifiers.alt(),
+        logo: modifiers.logo()
+    }
+}
+
+fn key_code(keycode: VirtualKeyCode) -> Option<KeyCode> {
+    let letter = match keycode {
+        VirtualKeyCode::Left => return Some(KeyCode::Left),
+        VirtualKeyCode::Right => return Some(KeyCode::Right),
+        VirtualKeyCode::Up => return Some(KeyCode::Up),
+        VirtualKeyCode::Down => return Some(KeyCode::Down),
+        VirtualKeyCode::Home => return Some(KeyCode::Home),
+        VirtualKeyCode::End => return Some(KeyCode::End),
+        VirtualKeyCode::Back => return Some(KeyCode::Backspace),
+        VirtualKeyCode::Delete => return Some(KeyCode::Delete),
+        VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => return Some(KeyCode::Enter),
+        VirtualKeyCode::Escape => return Some(KeyCode::Escape),
+        VirtualKeyCode::Tab => return Some(KeyCode::Tab),
+        VirtualKeyCode::A => 'a',
+        VirtualKeyCode::B => 'b',
+        VirtualKeyCode::C => 'c',
+        VirtualKeyCode::D => 'd',
+        VirtualKeyCode::E => 'e',
+        VirtualKeyCode::F => 'f',
+        VirtualKeyCode::G => 'g',
+        VirtualKeyCode::H => 'h',
+        VirtualKeyCode::I => 'i',
+        VirtualKeyCode::J => 'j',
+        VirtualKeyCode::K => 'k',
+        VirtualKeyCode::L => 'l',
+        VirtualKeyCode::M => 'm',
+        VirtualKeyCode::N => 'n',
+        VirtualKeyCode::O => 'o',
+        VirtualKeyCode::P => 'p',
+        VirtualKeyCode::Q => 'q',
+        VirtualKeyCode::R => 'r',
+        VirtualKeyCode::S => 's',
+        VirtualKeyCode::T => 't',
+        VirtualKeyCode::U => 'u',
+        VirtualKeyCode::V => 'v',
+        VirtualKeyCode::W => 'w',
+        VirtualKeyCode::X => 'x',
+        VirtualKeyCode::Y => 'y',
+        VirtualKeyCode::Z => 'z',
+        _ => return None
+    };
+    Some(KeyCode::Letter(letter))
+}
\ No newline at end of file
@@ -30,9 +30,12 @@ impl ApplicationBundleBuilder for PathFinderRendererBuilder {
 
     fn setup_rendering_systems(&self, builder: &mut Builder) {
         builder
+            .add_thread_local(remove_invisible_primitives_system())
             .add_thread_local(queue_render_rectangle_primitives_system())
             .add_thread_local(queue_render_circle_primitives_system())
+            .add_thread_local(queue_render_layout_outline_primitives_system())
             .add_thread_local(queue_render_text_primitives_system())
+            .add_thread_local(queue_render_text_input_primitives_system())
             .add_thread_local(render_primitives_system());
     }
 
@@ -44,9 +47,13 @@ impl ApplicationBundleBuilder for PathFinderRendererBuilder {
 
     fn setup_resources(&self, resources: &mut Resources, event_channel: &mut EventChannel<SystemEvent>) -> Result<(), ZodiacError>  {
         let mut event_loop = create_system_event_loop();
+        register_fonts(resources, |_| Ok(()))?;
+        let renderer = create_pathfinder_renderer(&mut event_loop, &resources.get::<FontRegistry>().unwrap())?;
         
-        resources.insert(create_pathfinder_renderer(&mut event_loop)?);
+        resources.insert(renderer);
         resources.insert(event_loop);
+        resources.insert(create_input_tracker());
+        register_system_clipboard(resources);
         resources.insert(create_pathfinder_event_reader_registry(event_channel));
         resources.insert(create_pathfinder_render_queue());
         
@@ -57,5 +64,8 @@ impl ApplicationBundleBuilder for PathFinderRendererBuilder {
         world_serializer.register_component::<Circle>(stringify!(Circle));
         world_serializer.register_component::<Rectangle>(stringify!(Rectangle));
         world_serializer.register_component::<Text>(stringify!(Text));
+        world_serializer.register_component::<RichText>(stringify!(RichText));
+        world_serializer.register_component::<Span>(stringify!(Span));
+        world_serializer.register_component::<TextInput>(stringify!(TextInput));
     }
 }
\ No newline at end of file
@@ -13,7 +13,47 @@ pub struct RenderPrimitive {
 pub enum RenderPrimitiveDefinition {
     Rectangle(Vector2F, Vector2F, ColorU, ColorU, u16),
     Circle(Vector2F, Vector2F, ColorU, ColorU, u16),
-    Text(Vector2F, Vector2F, ColorU, String, u8),
+    Text(Vector2F, Vector2F, Vec<RenderTextRun>, Vec<RectF>, ColorU),
+    TextInput(Vector2F, Vector2F, Vec<RenderTextRun>, Vec<RectF>, Option<RectF>, ColorU),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderTextRun {
+    pub position: Vector2F,
+    pub dimensions: Vector2F,
+    pub baseline: f32,
+    pub text: String,
+    pub font: FontId,
+    pub font_size: f32,
+    pub colour: ColorU,
+    pub underline: bool,
+    pub background: Option<ColorU>
+}
+
+impl RenderTextRun {
+    pub fn new(position: [u16; 2], dimensions: [u16; 2], baseline: u16, text: &str, font: FontId, font_size: u8, colour: Colour) -> Self {
+        Self {
+            position: vec2f(position[0] as f32, position[1] as f32),
+            dimensions: vec2f(dimensions[0] as f32, dimensions[1] as f32),
+            baseline: baseline as f32,
+            text: text.to_string(),
+            font,
+            font_size: font_size as f32,
+            colour: rgbau(colour.r, colour.g, colour.b, colour.a),
+            underline: false,
+            background: None
+        }
+    }
+
+    pub fn with_underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    pub fn with_background(mut self, background: Option<Colour>) -> Self {
+        self.background = background.map(|background| rgbau(background.r, background.g, background.b, background.a));
+        self
+    }
 }
 
 pub fn create_pathfinder_render_queue() -> PathFinderRenderQueue {
@@ -25,6 +65,12 @@ pub fn create_pathfinder_render_queue() -> PathFinderRenderQueue {
 pub struct PathFinderRenderQueue {
 }
 
+fn raster_rect((position, dimensions): ([u16; 2], [u16; 2])) -> RectF {
+    RectF::new(
+        vec2f(position[0] as f32, position[1] as f32),
+        vec2f(dimensions[0] as f32, dimensions[1] as f32))
+}
+
 impl PathFinderRenderQueue {
     fn queue_primitive_for_render(&mut self, command_buffer: &mut CommandBuffer, entity: &Entity, to_queue: RenderPrimitiveDefinition) {
         debug!("Queueing: {:?}", to_queue);
@@ -82,19 +128,42 @@ impl PathFinderRenderQueue {
         entity: &Entity,
         position: [u16; 2],
         dimensions: [u16; 2],
-        colour: Colour,
-        text: String,
-        font_size: u8) {
+        runs: Vec<RenderTextRun>,
+        selections: Vec<([u16; 2], [u16; 2])>,
+        colour: Colour) {
             self.queue_primitive_for_render(
                 command_buffer,
                 entity,
                 RenderPrimitiveDefinition::Text(
                     vec2f(position[0] as f32, position[1] as f32),
                     vec2f(dimensions[0] as f32, dimensions[1] as f32),
-                    rgbau(colour.r, colour.g, colour.b, colour.a),
-                    text,
-                    font_size
+                    runs,
+                    selections.into_iter().map(raster_rect).collect(),
+                    rgbau(colour.r, colour.g, colour.b, colour.a)
                 ));
 
     }
+
+    pub fn queue_text_input_for_render(
+        &mut self,
+        command_buffer: &mut CommandBuffer,
+        entity: &Entity,
+        position: [u16; 2],
+        dimensions: [u16; 2],
+        runs: Vec<RenderTextRun>,
+        selections: Vec<([u16; 2], [u16; 2])>,
+        caret: Option<([u16; 2], [u16; 2])>,
+        colour: Colour) {
+            self.queue_primitive_for_render(
+                command_buffer,
+                entity,
+                RenderPrimitiveDefinition::TextInput(
+                    vec2f(position[0] as f32, position[1] as f32),
+                    vec2f(dimensions[0] as f32, dimensions[1] as f32),
+                    runs,
+                    selections.into_iter().map(raster_rect).collect(),
+                    caret.map(raster_rect),
+                    rgbau(colour.r, colour.g, colour.b, colour.a)
+                ));
+    }
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
+use std::sync::Arc;
 use log::*;
 use glutin::event_loop::*;
 use pathfinder_canvas::*;
+use pathfinder_canvas::TextAlign;
+use font_kit::font::Font;
 use zodiac::*;
 
 use crate::*;
@@ -8,12 +11,13 @@ use crate::display::*;
 
 const PI2:f32 = std::f64::consts::PI as f32 * 2.0;
 
-pub fn create_pathfinder_renderer(event_loop: &EventLoop<()>) -> Result<PathFinderRenderer, RendererError> {
-    PathFinderRenderer::new(event_loop)
+pub fn create_pathfinder_renderer(event_loop: &EventLoop<()>, fonts: &FontRegistry) -> Result<PathFinderRenderer, RendererError> {
+    PathFinderRenderer::new(event_loop, fonts)
 }
 
 pub struct PathFinderRenderer {
     display: Display,
+    fonts: Vec::<Font>,
     primitives: Vec::<RenderPrimitive>
 }
 
@@ -24,11 +28,12 @@ impl Renderer for PathFinderRenderer {
 }
 
 impl PathFinderRenderer {
-    pub fn new(event_loop: &EventLoop<()>) -> Result<Self, RendererError> {
+    pub fn new(event_loop: &EventLoop<()>, fonts: &FontRegistry) -> Result<Self, RendererError> {
         let display = create_display(event_loop,  WrappedDimensions::from((640, 480)));
         
         let renderer = Self {
             display: display,
+            fonts: load_fonts(fonts)?,
             primitives: vec!()
         };
 
@@ -48,6 +53,7 @@ impl PathFinderRenderer {
     pub fn render(&mut self, primitives: Vec::<RenderPrimitive>) -> Result<(), RendererError> {  
         let draw_frame_start = std::time::Instant::now();
         let display = &mut self.display;
+        let fonts = &self.fonts;
         
         display.render_canvas(|canvas| {
             for primitive in &primitives {
@@ -77,12 +83,29 @@ impl PathFinderRenderer {
                         path.close_path();
                         canvas.stroke_path(path);
                     }
-                    RenderPrimitiveDefinition::Text(position, _, colour, text, font_size) => {
-                        info!("Rendering text {:?}, {:?}, {:?}", position, colour, text);
-                        canvas.set_font_size(*font_size as f32);
-                        canvas.set_fill_style(*colour);
-                        canvas.fill_text(text, *position);
-                        
+                    RenderPrimitiveDefinition::Text(position, dimensions, runs, selections, colour) => {
+                        info!("Rendering text {:?}, {:?}", position, runs);
+                        let mut clip = Path2D::new();
+                        clip.rect(RectF::new(*position, *dimensions));
+                        canvas.save();
+                        canvas.clip_path(clip, FillRule::Winding);
+                        fill_selections(canvas, selections, *colour);
+                        fill_text_runs(canvas, fonts, runs);
+                        canvas.restore();
+                    }
+                    RenderPrimitiveDefinition::TextInput(position, dimensions, runs, selections, caret, colour) => {
+                        info!("Rendering text input {:?}, {:?}", position, runs);
+                        let mut clip = Path2D::new();
+                        clip.rect(RectF::new(*position, *dimensions));
+                        canvas.save();
+                        canvas.clip_path(clip, FillRule::Winding);
+                        fill_selections(canvas, selections, *colour);
+                        fill_text_runs(canvas, fonts, runs);
+                        if let Some(caret) = caret {
+                            canvas.set_fill_style(*colour);
+                            canvas.fill_rect(*caret);
+                        }
+                        canvas.restore();
                     }
                 }
             }
@@ -95,4 +118,37 @@ impl PathFinderRenderer {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+fn fill_selections(canvas: &mut CanvasRenderingContext2D, selections: &[RectF], colour: ColorU) {
+    canvas.set_fill_style(rgbau(colour.r, colour.g, colour.b, colour.a / 4));
+    for selection in selections {
+        canvas.fill_rect(*selection);
+    }
+}
+
+fn fill_text_runs(canvas: &mut CanvasRenderingContext2D, fonts: &[Font], runs: &[RenderTextRun]) {
+    canvas.set_text_align(TextAlign::Left);
+    for run in runs {
+        if let Some(background) = run.background {
+            canvas.set_fill_style(background);
+            canvas.fill_rect(RectF::new(run.position, run.dimensions));
+        }
+        let baseline = run.position + vec2f(0.0, run.baseline);
+        canvas.set_font(fonts[run.font.id].clone());
+        canvas.set_font_size(run.font_size);
+        canvas.set_fill_style(run.colour);
+        canvas.fill_text(&run.text, baseline);
+        if run.underline {
+            let thickness = (run.font_size / 16.0).max(1.0);
+            canvas.fill_rect(RectF::new(baseline + vec2f(0.0, thickness), vec2f(run.dimensions.x(), thickness)));
+        }
+    }
+}
+
+fn load_fonts(fonts: &FontRegistry) -> Result<Vec::<Font>, RendererError> {
+    fonts
+        .faces()
+        .map(|(_, face)| Font::from_bytes(Arc::new(face.data().to_vec()), 0).map_err(|_| RendererError::FailedToLoadFont))
+        .collect()
+}
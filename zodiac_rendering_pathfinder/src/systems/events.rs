@@ -13,12 +13,26 @@ use crate::*;
 pub fn event_loop(
     #[resource] event_loop: &mut EventLoop<()>,
     #[resource] event_producer: &mut SystemEventProducer,
-    #[resource] event_channel: &mut EventChannel::<SystemEvent>) {
+    #[resource] event_channel: &mut EventChannel::<SystemEvent>,
+    #[resource] input_tracker: &mut InputTracker,
+    #[resource] renderer: &PathFinderRenderer) {
+    let mut scale_factor = renderer.get_scale_factor() as f64;
     event_loop.run_return(|event, _, flow| {
         match event {
             Event::WindowEvent { window_id: _, event} => {
                 debug!("Window event {:?}", event);
-                event_producer.push(WrappedSystemEvent::from(event).into());
+                if let WindowEvent::ScaleFactorChanged { scale_factor: changed_scale_factor, .. } = event {
+                    scale_factor = changed_scale_factor;
+                }
+                if !input_tracker.receive_text(&event) {
+                    if let Some(text) = input_tracker.take_received_text() {
+                        event_producer.push(text);
+                    }
+                    match input_tracker.translate(&event, scale_factor) {
+                        Some(input) => event_producer.push(input),
+                        None => event_producer.push(WrappedSystemEvent::from((event, scale_factor)).into())
+                    }
+                }
             }
             Event::DeviceEvent { device_id: _, event } => {
                 event_producer.push(WrappedSystemEvent::from(event).into());
@@ -28,13 +42,17 @@ pub fn event_loop(
         *flow = ControlFlow::Exit;
     });
 
+    if let Some(text) = input_tracker.take_received_text() {
+        event_producer.push(text);
+    }
     event_producer.drain_to(event_channel);
 }
 
-impl From<WindowEvent<'_>> for WrappedSystemEvent {
-    fn from(event: WindowEvent) -> Self {
+impl From<(WindowEvent<'_>, f64)> for WrappedSystemEvent {
+    fn from((event, scale_factor): (WindowEvent, f64)) -> Self {
         match event {
-            WindowEvent::Resized(size) => WrappedSystemEvent::from(SystemWindowEventType::RootWindowResize(WrappedDimensions::from(size).into())),
+            WindowEvent::Resized(size) => WrappedSystemEvent::from(SystemWindowEventType::RootWindowResize(WrappedDimensions::from((size, scale_factor)).into())),
+            WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size } => WrappedSystemEvent::from(SystemWindowEventType::RootWindowResize(WrappedDimensions::from((*new_inner_size, scale_factor)).into())),
             WindowEvent::CloseRequested => WrappedSystemEvent::from(SystemWindowEventType::CloseRequested),
             _ => WrappedSystemEvent::new(SystemEvent::Unused)
         }
@@ -2,10 +2,13 @@ use legion::*;
 use legion::systems::*;
 use zodiac::*;
 use crate::PathFinderRenderQueue;
+use crate::PathFinderRenderer;
 use crate::components::*;
+use crate::RenderPrimitive;
+use crate::RenderTextRun;
 
 #[system(for_each)]
-#[filter(component::<Rectangle>())]
+#[filter(component::<Rectangle>() & !component::<Invisible>())]
 pub fn queue_render_rectangle_primitives(
     entity: &Entity, 
     layout_change: &LayoutChange, 
@@ -13,19 +16,21 @@ pub fn queue_render_rectangle_primitives(
     stroke_colour: &StrokeColour,
     stroke_width: &StrokeWidth,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut PathFinderRenderQueue) {
+    #[resource] render_queue: &mut PathFinderRenderQueue,
+    #[resource] renderer: &PathFinderRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_rectangle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        [layout_change.width, layout_change.height],
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
         *colour,
         *stroke_colour, 
-        stroke_width.width);
+        rasterise(stroke_width.width as f32, scale_factor));
 }
 
 #[system(for_each)]
-#[filter(component::<Circle>())]
+#[filter(component::<Circle>() & !component::<Invisible>())]
 pub fn queue_render_circle_primitives(
     entity: &Entity,
     layout_change: &LayoutChange, 
@@ -33,35 +38,132 @@ pub fn queue_render_circle_primitives(
     stroke_colour: &StrokeColour,
     stroke_width: &StrokeWidth,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut PathFinderRenderQueue) {
+    #[resource] render_queue: &mut PathFinderRenderQueue,
+    #[resource] renderer: &PathFinderRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_circle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        layout_change.width,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor)[0],
         *colour,
         *stroke_colour, 
-        stroke_width.width);
+        rasterise(stroke_width.width as f32, scale_factor));
 }
 
 
 
 #[system(for_each)]
-#[filter(component::<Text>())]
+#[filter((component::<Text>() | component::<RichText>()) & !component::<Invisible>())]
 pub fn queue_render_text_primitives (
     entity: &Entity,
     layout_change: &LayoutChange, 
     colour: &Colour,
-    content: &Content,
-    font_size: &FontSize,
+    text_lines: &TextLines,
+    selectable: Option<&Selectable>,
+    text_selection: Option<&TextSelection>,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut PathFinderRenderQueue) {
+    #[resource] render_queue: &mut PathFinderRenderQueue,
+    #[resource] renderer: &PathFinderRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    let selections = match (selectable, text_selection) {
+        (Some(selectable), Some(text_selection)) if selectable.selectable => {
+            let (start, end) = text_selection.selection();
+            selection_rects(text_lines, start, end, layout_change.left, layout_change.top)
+        },
+        _ => vec!()
+    };
     render_queue.queue_text_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        [layout_change.width, layout_change.height],
-        *colour,
-        content.text.clone(),
-        font_size.size);
-}
\ No newline at end of file
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        render_text_runs(text_lines, [layout_change.left, layout_change.top], *colour, scale_factor),
+        selections
+            .iter()
+            .map(|selection| (selection.raster_position(scale_factor), selection.raster_dimensions(scale_factor)))
+            .collect(),
+        *colour);
+}
+
+#[system(for_each)]
+#[filter(component::<TextInput>() & !component::<Invisible>())]
+pub fn queue_render_text_input_primitives (
+    entity: &Entity,
+    layout_change: &LayoutChange, 
+    colour: &Colour,
+    text_lines: &TextLines,
+    text_edit_state: &TextEditState,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut PathFinderRenderQueue,
+    #[resource] renderer: &PathFinderRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    let geometry = text_edit_geometry(text_lines, text_edit_state, layout_change.left, layout_change.top);
+    let text_colour = if text_edit_state.is_empty() { placeholder_colour(*colour) } else { *colour };
+    render_queue.queue_text_input_for_render(
+        command_buffer,
+        entity,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        render_text_runs(
+            text_lines,
+            [layout_change.left - text_edit_state.scroll_left, layout_change.top - text_edit_state.scroll_top],
+            text_colour,
+            scale_factor),
+        geometry.selections
+            .iter()
+            .map(|selection| (selection.raster_position(scale_factor), selection.raster_dimensions(scale_factor)))
+            .collect(),
+        geometry.caret.map(|caret| (caret.raster_position(scale_factor), caret.raster_dimensions(scale_factor))),
+        *colour);
+}
+
+fn render_text_runs(text_lines: &TextLines, origin: [f32; 2], colour: Colour, scale_factor: f32) -> Vec<RenderTextRun> {
+    text_lines.runs
+        .iter()
+        .map(|run| {
+            let left = rasterise(origin[0] + run.left, scale_factor);
+            let top = rasterise(origin[1] + run.top, scale_factor);
+            RenderTextRun::new(
+                [left, top],
+                [
+                    rasterise(origin[0] + run.left + run.width, scale_factor).saturating_sub(left),
+                    rasterise(origin[1] + run.top + text_lines.line_height, scale_factor).saturating_sub(top)
+                ],
+                rasterise(text_lines.baseline, scale_factor),
+                &run.text,
+                run.font,
+                rasterise_font_size(run.font_size as u8, scale_factor),
+                run.colour.unwrap_or(colour))
+                .with_underline(run.underline)
+                .with_background(run.background)
+        })
+        .collect()
+}
+
+#[system(for_each)]
+#[filter(component::<LayoutOutline>())]
+pub fn queue_render_layout_outline_primitives(
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    stroke_colour: &StrokeColour,
+    stroke_width: &StrokeWidth,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut PathFinderRenderQueue,
+    #[resource] renderer: &PathFinderRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    render_queue.queue_rectangle_for_render(
+        command_buffer,
+        entity,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        Colour::from((0, 0, 0, 0)),
+        *stroke_colour,
+        rasterise(stroke_width.width as f32, scale_factor));
+}
+
+#[system(for_each)]
+#[filter(component::<Invisible>() & component::<RenderPrimitive>())]
+pub fn remove_invisible_primitives(entity: &Entity, command_buffer: &mut CommandBuffer) {
+    command_buffer.remove_component::<RenderPrimitive>(*entity);
+}
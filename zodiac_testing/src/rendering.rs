@@ -16,7 +16,76 @@ pub struct RenderPrimitive {
 pub enum RenderPrimitiveType {
     Rectangle([u16; 2], [u16; 2], [u8; 4], [u8; 4], u16, [u16; 4]),
     Circle([u16; 2], u16, [u8; 4], [u8; 4], u16),
-    Text([u16; 2], [u16; 2], [u8; 4], String, u8),
+    Text([u16; 2], [u16; 2], [u8; 4], Vec<RenderTextRun>, u8, Direction, Vec<([u16; 2], [u16; 2])>),
+    TextInput([u16; 2], [u16; 2], [u8; 4], Vec<RenderTextRun>, u8, Direction, RenderTextEditing),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RenderTextRun {
+    position: [u16; 2],
+    text: String,
+    font: FontId,
+    font_size: Option<u8>,
+    colour: Option<[u8; 4]>,
+    underline: bool,
+    background: Option<[u8; 4]>
+}
+
+impl RenderTextRun {
+    pub fn new(position: [u16; 2], text: &str) -> Self {
+        Self {
+            position,
+            text: text.to_string(),
+            font: FontId::default(),
+            font_size: None,
+            colour: None,
+            underline: false,
+            background: None
+        }
+    }
+
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: u8) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    pub fn with_colour(mut self, colour: [u8; 4]) -> Self {
+        self.colour = Some(colour);
+        self
+    }
+
+    pub fn with_underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn with_background(mut self, background: [u8; 4]) -> Self {
+        self.background = Some(background);
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RenderTextEditing {
+    caret: Option<([u16; 2], [u16; 2])>,
+    selections: Vec<([u16; 2], [u16; 2])>
+}
+
+impl RenderTextEditing {
+    pub fn with_caret(mut self, position: [u16; 2], dimensions: [u16; 2]) -> Self {
+        self.caret = Some((position, dimensions));
+        self
+    }
+
+    pub fn with_selection(mut self, position: [u16; 2], dimensions: [u16; 2]) -> Self {
+        self.selections.push((position, dimensions));
+        self
+    }
 }
 
 pub fn create_test_render_queue() -> TestRenderQueue {
@@ -51,9 +120,28 @@ impl RenderPrimitive {
         position: [u16; 2],
         dimensions: [u16; 2],
         colour: [u8; 4],
-        text: String,
-        font_size: u8) -> Self {
-        RenderPrimitive { render_type: RenderPrimitiveType::Text(position, dimensions, colour, text, font_size) }
+        runs: Vec<RenderTextRun>,
+        font_size: u8,
+        direction: Direction) -> Self {
+        RenderPrimitive { render_type: RenderPrimitiveType::Text(position, dimensions, colour, runs, font_size, direction, vec!()) }
+    }
+
+    pub fn with_selection(mut self, position: [u16; 2], dimensions: [u16; 2]) -> Self {
+        if let RenderPrimitiveType::Text(_, _, _, _, _, _, selections) = &mut self.render_type {
+            selections.push((position, dimensions));
+        }
+        self
+    }
+
+    pub fn text_input(
+        position: [u16; 2],
+        dimensions: [u16; 2],
+        colour: [u8; 4],
+        runs: Vec<RenderTextRun>,
+        font_size: u8,
+        direction: Direction,
+        editing: RenderTextEditing) -> Self {
+        RenderPrimitive { render_type: RenderPrimitiveType::TextInput(position, dimensions, colour, runs, font_size, direction, editing) }
     }
 }
 
@@ -114,17 +202,48 @@ impl TestRenderQueue {
         position: [u16; 2],
         dimensions: [u16; 2],
         colour: Colour,
-        text: String,
-        font_size: u8) {
+        runs: Vec<RenderTextRun>,
+        font_size: u8,
+        direction: Direction,
+        selections: Vec<([u16; 2], [u16; 2])>) {
+        self.queue_primitive_for_render(
+            command_buffer,
+            entity,
+            selections
+                .into_iter()
+                .fold(
+                    RenderPrimitive::text(
+                        position,
+                        dimensions,
+                        colour.into(),
+                        runs,
+                        font_size,
+                        direction),
+                    |primitive, (position, dimensions)| primitive.with_selection(position, dimensions)));
+    }
+
+    pub fn queue_text_input_for_render(
+        &mut self,
+        command_buffer: &mut CommandBuffer,
+        entity: &Entity,
+        position: [u16; 2],
+        dimensions: [u16; 2],
+        colour: Colour,
+        runs: Vec<RenderTextRun>,
+        font_size: u8,
+        direction: Direction,
+        editing: RenderTextEditing) {
         self.queue_primitive_for_render(
             command_buffer,
             entity,
-            RenderPrimitive::text(
+            RenderPrimitive::text_input(
                 position,
                 dimensions,
                 colour.into(),
-                text,
-                font_size));
+                runs,
+                font_size,
+                direction,
+                editing));
     }
 }
 
@@ -160,9 +279,12 @@ impl ApplicationBundleBuilder for TestRendererBuilder {
 
     fn setup_rendering_systems(&self, builder: &mut Builder) {
         builder
+            .add_thread_local(remove_invisible_primitives_system())
             .add_thread_local(queue_render_rectangle_primitives_system())
             .add_thread_local(queue_render_circle_primitives_system())
-            .add_thread_local(queue_render_text_primitives_system());
+            .add_thread_local(queue_render_layout_outline_primitives_system())
+            .add_thread_local(queue_render_text_primitives_system())
+            .add_thread_local(queue_render_text_input_primitives_system());
     }
 
     fn setup_cleanup_systems(&self, _: &mut Builder) {
@@ -182,6 +304,9 @@ impl ApplicationBundleBuilder for TestRendererBuilder {
         world_serializer.register_component::<Circle>(stringify!(Circle));
         world_serializer.register_component::<Rectangle>(stringify!(Rectangle));
         world_serializer.register_component::<Text>(stringify!(Text));
+        world_serializer.register_component::<RichText>(stringify!(RichText));
+        world_serializer.register_component::<Span>(stringify!(Span));
+        world_serializer.register_component::<TextInput>(stringify!(TextInput));
     }
 }
 
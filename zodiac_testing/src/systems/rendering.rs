@@ -2,10 +2,14 @@ use legion::*;
 use legion::systems::*;
 use zodiac::*;
 use crate::TestRenderQueue;
+use crate::TestRenderer;
 use crate::components::*;
+use crate::RenderPrimitive;
+use crate::RenderTextRun;
+use crate::RenderTextEditing;
 
 #[system(for_each)]
-#[filter(component::<Rectangle>())]
+#[filter(component::<Rectangle>() & !component::<Invisible>())]
 pub fn queue_render_rectangle_primitives(
     entity: &Entity, 
     layout_change: &LayoutChange, 
@@ -14,20 +18,27 @@ pub fn queue_render_rectangle_primitives(
     stroke_width: &StrokeWidth,
     corner_radii: &CornerRadii,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut TestRenderQueue) {
+    #[resource] render_queue: &mut TestRenderQueue,
+    #[resource] renderer: &TestRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_rectangle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        [layout_change.width, layout_change.height],
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
         *colour,
         *stroke_colour, 
-        stroke_width.width, 
-        [corner_radii.left_top, corner_radii.right_top, corner_radii.right_bottom, corner_radii.left_bottom]);
+        rasterise(stroke_width.width as f32, scale_factor), 
+        [
+            rasterise(corner_radii.left_top as f32, scale_factor),
+            rasterise(corner_radii.right_top as f32, scale_factor),
+            rasterise(corner_radii.right_bottom as f32, scale_factor),
+            rasterise(corner_radii.left_bottom as f32, scale_factor)
+        ]);
 }
 
 #[system(for_each)]
-#[filter(component::<Circle>())]
+#[filter(component::<Circle>() & !component::<Invisible>())]
 pub fn queue_render_circle_primitives(
     entity: &Entity,
     layout_change: &LayoutChange, 
@@ -35,33 +46,156 @@ pub fn queue_render_circle_primitives(
     stroke_colour: &StrokeColour,
     stroke_width: &StrokeWidth,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut TestRenderQueue) {
+    #[resource] render_queue: &mut TestRenderQueue,
+    #[resource] renderer: &TestRenderer) {
+    let scale_factor = renderer.get_scale_factor();
     render_queue.queue_circle_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        layout_change.width,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor)[0],
         *colour,
         *stroke_colour, 
-        stroke_width.width);
+        rasterise(stroke_width.width as f32, scale_factor));
 }
 
 #[system(for_each)]
-#[filter(component::<Text>())]
+#[filter((component::<Text>() | component::<RichText>()) & !component::<Invisible>())]
 pub fn queue_render_text_primitives (
     entity: &Entity,
     layout_change: &LayoutChange, 
     colour: &Colour,
-    content: &Content,
+    text_lines: &TextLines,
     font_size: &FontSize,
+    current_direction: &CurrentDirection,
+    text_selection: Option<&TextSelection>,
     command_buffer: &mut CommandBuffer,
-    #[resource] render_queue: &mut TestRenderQueue) {
+    #[resource] render_queue: &mut TestRenderQueue,
+    #[resource] renderer: &TestRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    let raster_font_size = rasterise_font_size(font_size.size, scale_factor);
+    let selections = match text_selection {
+        Some(text_selection) => {
+            let (start, end) = text_selection.selection();
+            selection_rects(text_lines, start, end, layout_change.left, layout_change.top)
+        },
+        None => vec!()
+    };
     render_queue.queue_text_for_render(
         command_buffer,
         entity,
-        [layout_change.left, layout_change.top],
-        [layout_change.width, layout_change.height],
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
         *colour,
-        content.text.clone(),
-        font_size.size);
-}
\ No newline at end of file
+        text_lines.runs
+            .iter()
+            .map(|run| render_text_run(
+                run,
+                [
+                    rasterise(layout_change.left + run.left, scale_factor),
+                    rasterise(layout_change.top + run.top, scale_factor)
+                ],
+                raster_font_size,
+                *colour,
+                scale_factor))
+            .collect(),
+        raster_font_size,
+        current_direction.direction,
+        selections
+            .iter()
+            .map(|selection| (selection.raster_position(scale_factor), selection.raster_dimensions(scale_factor)))
+            .collect());
+}
+
+#[system(for_each)]
+#[filter(component::<TextInput>() & !component::<Invisible>())]
+pub fn queue_render_text_input_primitives (
+    entity: &Entity,
+    layout_change: &LayoutChange, 
+    colour: &Colour,
+    text_lines: &TextLines,
+    font_size: &FontSize,
+    current_direction: &CurrentDirection,
+    text_edit_state: &TextEditState,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut TestRenderQueue,
+    #[resource] renderer: &TestRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    let raster_font_size = rasterise_font_size(font_size.size, scale_factor);
+    let geometry = text_edit_geometry(text_lines, text_edit_state, layout_change.left, layout_change.top);
+    let mut editing = geometry.selections
+        .iter()
+        .fold(RenderTextEditing::default(), |editing, selection| editing.with_selection(
+            selection.raster_position(scale_factor),
+            selection.raster_dimensions(scale_factor)));
+    if let Some(caret) = geometry.caret {
+        editing = editing.with_caret(caret.raster_position(scale_factor), caret.raster_dimensions(scale_factor));
+    }
+    render_queue.queue_text_input_for_render(
+        command_buffer,
+        entity,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        if text_edit_state.is_empty() { placeholder_colour(*colour) } else { *colour },
+        text_lines.runs
+            .iter()
+            .map(|run| render_text_run(
+                run,
+                [
+                    rasterise(layout_change.left + run.left - text_edit_state.scroll_left, scale_factor),
+                    rasterise(layout_change.top + run.top - text_edit_state.scroll_top, scale_factor)
+                ],
+                raster_font_size,
+                *colour,
+                scale_factor))
+            .collect(),
+        raster_font_size,
+        current_direction.direction,
+        editing);
+}
+
+fn render_text_run(run: &TextRun, position: [u16; 2], font_size: u8, colour: Colour, scale_factor: f32) -> RenderTextRun {
+    let mut render_run = RenderTextRun::new(position, &run.text).with_font(run.font);
+    let run_font_size = rasterise_font_size(run.font_size as u8, scale_factor);
+    if run_font_size != font_size {
+        render_run = render_run.with_font_size(run_font_size);
+    }
+    if let Some(run_colour) = run.colour.filter(|run_colour| *run_colour != colour) {
+        render_run = render_run.with_colour(run_colour.into());
+    }
+    if run.underline {
+        render_run = render_run.with_underline();
+    }
+    if let Some(background) = run.background {
+        render_run = render_run.with_background(background.into());
+    }
+    render_run
+}
+
+#[system(for_each)]
+#[filter(component::<LayoutOutline>())]
+pub fn queue_render_layout_outline_primitives(
+    entity: &Entity,
+    layout_change: &LayoutChange,
+    stroke_colour: &StrokeColour,
+    stroke_width: &StrokeWidth,
+    command_buffer: &mut CommandBuffer,
+    #[resource] render_queue: &mut TestRenderQueue,
+    #[resource] renderer: &TestRenderer) {
+    let scale_factor = renderer.get_scale_factor();
+    render_queue.queue_rectangle_for_render(
+        command_buffer,
+        entity,
+        layout_change.raster_position(scale_factor),
+        layout_change.raster_dimensions(scale_factor),
+        Colour::from((0, 0, 0, 0)),
+        *stroke_colour,
+        rasterise(stroke_width.width as f32, scale_factor),
+        [0, 0, 0, 0]);
+}
+
+#[system(for_each)]
+#[filter(component::<Invisible>() & component::<RenderPrimitive>())]
+pub fn remove_invisible_primitives(entity: &Entity, command_buffer: &mut CommandBuffer) {
+    command_buffer.remove_component::<RenderPrimitive>(*entity);
+}
@@ -1,6 +1,8 @@
+use std::sync::atomic::{ AtomicU16, Ordering };
 use legion::*;
 use mox::mox;
 use zodiac_testing::*;
+use zodiac_layout::*;
 use zodiac::*;
 
 #[topo::nested]
@@ -320,4 +322,1061 @@ fn vertical_layout_for_sized_children() {
     assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 25], [100, 40], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
     assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 65], [100, 35], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
     assert_eq!(changes.len(), 3);
-}
\ No newline at end of file
+}
+
+#[topo::nested]
+fn horizontal_layout_for_oversized_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <rect
+                    width=60
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    width=70
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn horizontal_layout_for_oversized_children() {
+    let mut runner = Application::new(TestState::default(), horizontal_layout_for_oversized_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    let diagnostics: Vec::<LayoutDiagnostics> = <&LayoutDiagnostics>::query()
+        .iter(runner.world_mut())
+        .map(|diagnostics| *diagnostics)
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [60, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([60, 0], [70, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([100, 0], [0, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+    assert_eq!(diagnostics, vec!(LayoutDiagnostics { overflow_width: 30.0, overflow_height: 0.0 }));
+}
+
+#[topo::nested]
+fn vertical_layout_for_oversized_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <rect
+                    height=150
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_for_oversized_children() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_for_oversized_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    let diagnostics: Vec::<LayoutDiagnostics> = <&LayoutDiagnostics>::query()
+        .iter(runner.world_mut())
+        .map(|diagnostics| *diagnostics)
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 150], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 100], [100, 0], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(diagnostics, vec!(LayoutDiagnostics { overflow_width: 0.0, overflow_height: 50.0 }));
+}
+
+#[topo::nested]
+fn horizontal_layout_distributes_remainder_pixels_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn horizontal_layout_distributes_remainder_pixels() {
+    let mut runner = Application::new(TestState::default(), horizontal_layout_distributes_remainder_pixels_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [33, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([33, 0], [34, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([67, 0], [33, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[test]
+fn horizontal_layout_is_scaled_to_physical_pixels() {
+    let mut runner = Application::new(TestState::default(), horizontal_layout_for_sized_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(200, 200).with_scale_factor(2.0)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [50, 200], [255, 255, 255, 25], [50, 75, 255, 255], 4, [100, 0, 100, 100])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([50, 0], [80, 200], [255, 255, 255, 25], [50, 75, 255, 255], 4, [100, 0, 100, 100])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([130, 0], [70, 200], [255, 255, 255, 25], [50, 75, 255, 255], 4, [100, 0, 100, 100])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn vertical_layout_for_filling_child_stack_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <horizontal_stack>
+                    <rect
+                        width=20
+                        height=20
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                    <rect
+                        width=20
+                        height=20
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </horizontal_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_for_filling_child_stack() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_for_filling_child_stack_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [20, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([20, 0], [20, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 40], [100, 60], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn vertical_layout_for_content_wrapping_child_stack_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <horizontal_stack sizing=Sizing::WrapContent>
+                    <rect
+                        width=20
+                        height=20
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                    <rect
+                        width=20
+                        height=30
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </horizontal_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_for_content_wrapping_child_stack() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_for_content_wrapping_child_stack_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    let constraints: Vec::<CurrentLayoutConstraints> = <&CurrentLayoutConstraints>::query()
+        .filter(component::<Sizing>())
+        .iter(runner.world_mut())
+        .map(|constraints| *constraints)
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [20, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([20, 0], [20, 30], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 30], [100, 70], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+    assert_eq!(constraints, vec!(CurrentLayoutConstraints { left: 0.0, top: 0.0, width: 40.0, height: 30.0 }));
+}
+
+const DIAGONAL_LAYOUT: u16 = 1;
+
+#[derive(Default)]
+struct DiagonalLayout {
+}
+
+impl LayoutAlgorithm for DiagonalLayout {
+    fn measure(&self, _: &MeasurementMaps, _: &Entity, _: LayoutAxis, child_sizes: &[(Entity, f32)]) -> f32 {
+        sum_of_sizes(child_sizes)
+    }
+
+    fn arrange(&self, maps: &LayoutMaps, entity: &Entity, constraints: &LayoutConstraints) -> Arrangement {
+        let children: Vec::<Entity> = maps.relationship_map.get_children(entity).collect();
+        let width = constraints.width / children.len().max(1) as f32;
+        let height = constraints.height / children.len().max(1) as f32;
+
+        Arrangement::new(children
+            .into_iter()
+            .enumerate()
+            .map(|(index, child)| (child, LayoutConstraints {
+                left: constraints.left + width * index as f32,
+                top: constraints.top + height * index as f32,
+                width,
+                height
+            }))
+            .collect())
+    }
+}
+
+element! {
+    <diagonal_stack>
+    [LayoutContent::custom(DIAGONAL_LAYOUT)]
+}
+
+#[topo::nested]
+fn custom_layout_for_none_sized_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <diagonal_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </diagonal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn custom_layout_for_none_sized_children() {
+    let mut runner = Application::new(TestState::default(), custom_layout_for_none_sized_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(custom_layout(LayoutType::Custom(DIAGONAL_LAYOUT), DiagonalLayout::default()))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [50, 50], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([50, 50], [50, 50], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 2);
+}
+
+#[topo::nested]
+fn grid_layout_for_placed_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <grid
+                columns=vec![GridTrack::Fixed(20), GridTrack::Fraction(1)]
+                rows=vec![GridTrack::Auto, GridTrack::Fraction(1)]
+                column_gap=10
+                row_gap=10
+            >
+                <rect
+                    height=30
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    column=1
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    row=1
+                    column_span=2
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </grid>
+        </root>
+    )
+}
+
+#[test]
+fn grid_layout_for_placed_children() {
+    let mut runner = Application::new(TestState::default(), grid_layout_for_placed_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [20, 30], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([30, 0], [70, 30], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 40], [100, 60], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn wrap_layout_for_sized_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <wrap_stack line_spacing=10 line_alignment=LineAlignment::Centre>
+                <rect
+                    width=40
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    width=40
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    width=40
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </wrap_stack>
+        </root>
+    )
+}
+
+#[test]
+fn wrap_layout_for_sized_children() {
+    let mut runner = Application::new(TestState::default(), wrap_layout_for_sized_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([10, 0], [40, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([50, 0], [40, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([30, 30], [40, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn dock_layout_for_docked_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <dock>
+                <rect
+                    dock=Dock::Top
+                    height=10
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    dock=Dock::Bottom
+                    height=10
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    dock=Dock::Left
+                    width=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </dock>
+        </root>
+    )
+}
+
+#[test]
+fn dock_layout_for_docked_children() {
+    let mut runner = Application::new(TestState::default(), dock_layout_for_docked_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 90], [100, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 10], [20, 80], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([20, 10], [80, 80], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 4);
+}
+
+#[topo::nested]
+fn layer_layout_for_aligned_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <layer_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    alignment=Alignment::Centre
+                    width=20
+                    height=10
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    alignment=Alignment::BottomRight
+                    width=10
+                    height=10
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </layer_stack>
+        </root>
+    )
+}
+
+#[test]
+fn layer_layout_for_aligned_children() {
+    let mut runner = Application::new(TestState::default(), layer_layout_for_aligned_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([40, 45], [20, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([90, 90], [10, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn canvas_layout_for_anchored_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <canvas>
+                <rect
+                    right=10
+                    bottom=10
+                    width=20
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    left=10
+                    right=10
+                    top=0
+                    height=5
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    center_x=0
+                    center_y=0
+                    width=20
+                    height=10
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </canvas>
+        </root>
+    )
+}
+
+#[test]
+fn canvas_layout_for_anchored_children() {
+    let mut runner = Application::new(TestState::default(), canvas_layout_for_anchored_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([70, 70], [20, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([10, 0], [80, 5], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([40, 45], [20, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn canvas_layout_for_anchored_container_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <canvas>
+                <horizontal_stack right=10 bottom=10 width=56 height=20>
+                    <rect
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </horizontal_stack>
+                <vertical_stack center_x=0 center_y=5 width=20 height=10>
+                    <rect
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </vertical_stack>
+            </canvas>
+        </root>
+    )
+}
+
+#[test]
+fn canvas_layout_for_anchored_container_children() {
+    let mut runner = Application::new(TestState::default(), canvas_layout_for_anchored_container_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([34, 70], [56, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([40, 50], [20, 10], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 2);
+}
+
+#[topo::nested]
+fn vertical_layout_for_aspect_ratio_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <rect
+                    width=40
+                    aspect_ratio=(2, 1)
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    aspect_ratio=(4, 1)
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_for_aspect_ratio_children() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_for_aspect_ratio_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [40, 20], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 20], [100, 25], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 45], [100, 55], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+static RESIZABLE_CHILD_WIDTH: AtomicU16 = AtomicU16::new(20);
+
+#[topo::nested]
+fn horizontal_layout_after_child_resize_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <rect
+                    width=RESIZABLE_CHILD_WIDTH.load(Ordering::Relaxed)
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn horizontal_layout_after_child_resize() {
+    let mut runner = Application::new(TestState::default(), horizontal_layout_after_child_resize_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+    RESIZABLE_CHILD_WIDTH.store(40, Ordering::Relaxed);
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [40, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([40, 0], [60, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 2);
+}
+
+#[topo::nested]
+fn horizontal_layout_for_right_to_left_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack direction=Direction::RightToLeft>
+                <rect
+                    width=25
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <canvas width=35>
+                    <rect
+                        left=5
+                        width=10
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </canvas>
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn horizontal_layout_for_right_to_left_children() {
+    let mut runner = Application::new(TestState::default(), horizontal_layout_for_right_to_left_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([75, 0], [25, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([35, 0], [40, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([20, 0], [10, 100], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 3);
+}
+
+#[topo::nested]
+fn vertical_layout_for_hidden_and_collapsed_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <rect
+                    height=20
+                    visibility=Visibility::Hidden
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    height=30
+                    visibility=Visibility::Collapsed
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    height=25
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_for_hidden_and_collapsed_children() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_for_hidden_and_collapsed_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 20], [100, 25], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 1);
+}
+
+#[topo::nested]
+fn layout_debugging_for_stacked_children_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <rect
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+                <rect
+                    height=20
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn layout_debugging_outlines_every_box() {
+    let mut runner = Application::new(TestState::default(), layout_debugging_for_stacked_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(layout_debugging().with_overlay())
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 100], [0, 0, 0, 0], [0, 170, 255, 255], 1, [0, 0, 0, 0])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 100], [0, 0, 0, 0], [255, 171, 0, 255], 1, [0, 0, 0, 0])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [100, 20], [0, 0, 0, 0], [158, 158, 158, 255], 1, [0, 0, 0, 0])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 20], [100, 20], [0, 0, 0, 0], [158, 158, 158, 255], 1, [0, 0, 0, 0])), true);
+    assert_eq!(changes.len(), 6);
+}
+
+#[test]
+fn layout_dump_indents_resolved_boxes() {
+    let mut runner = Application::new(TestState::default(), layout_debugging_for_stacked_children_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let relationship_map = runner.resources_mut().remove::<RelationshipMap>().unwrap();
+    let dump = dump_layout(runner.world_mut(), &relationship_map);
+    let lines: Vec<&str> = dump.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0].starts_with("Entity"), true);
+    assert_eq!(lines[0].ends_with("Canvas [0, 0, 100, 100]"), true);
+    assert_eq!(lines[1].starts_with("  Entity"), true);
+    assert_eq!(lines[1].ends_with("Vertical [0, 0, 100, 100]"), true);
+    assert_eq!(lines[2].starts_with("    Entity"), true);
+    assert_eq!(lines[2].ends_with("Renderable [0, 0, 100, 20]"), true);
+    assert_eq!(lines[3].ends_with("Renderable [0, 20, 100, 20]"), true);
+}
+
+static BOUNDED_CHILD_WIDTH: AtomicU16 = AtomicU16::new(20);
+
+#[topo::nested]
+fn vertical_layout_after_bounded_child_resize_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <horizontal_stack>
+                    <rect
+                        width=BOUNDED_CHILD_WIDTH.load(Ordering::Relaxed)
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                    <rect
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </horizontal_stack>
+                <horizontal_stack>
+                    <rect
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                    <rect
+                        colour=(255, 255, 255, 25)
+                        stroke_colour=(50, 75, 255, 255)
+                        stroke_width=2
+                        corner_radii=(50, 0, 50, 50)
+                    />
+                </horizontal_stack>
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn vertical_layout_after_bounded_child_resize() {
+    let mut runner = Application::new(TestState::default(), vertical_layout_after_bounded_child_resize_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 100)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let rendered: Vec::<Entity> = <Entity>::query()
+        .filter(component::<RenderPrimitive>())
+        .iter(runner.world_mut())
+        .copied()
+        .collect();
+
+    for entity in rendered {
+        runner.world_mut().entry(entity).unwrap().remove_component::<RenderPrimitive>();
+    }
+
+    BOUNDED_CHILD_WIDTH.store(40, Ordering::Relaxed);
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 0], [40, 50], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([40, 0], [60, 50], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+    assert_eq!(changes.len(), 2);
+}
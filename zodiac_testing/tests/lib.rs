@@ -1,2 +1,6 @@
 pub mod layout;
-pub mod text;
\ No newline at end of file
+pub mod text;
+pub mod shaping;
+pub mod text_input;
+pub mod text_selection;
+pub mod localisation;
\ No newline at end of file
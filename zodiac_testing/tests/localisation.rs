@@ -0,0 +1,198 @@
+use std::time::{ Duration, SystemTime, UNIX_EPOCH };
+use legion::*;
+use mox::mox;
+use zodiac_testing::*;
+use zodiac::*;
+
+const ENGLISH: &str = "
+# English messages
+greeting = Hello, {name}!
+short = ab
+files[one] = {count} file
+files[other] = {count} files
+updated = Updated {date}
+total = Total: {amount}
+";
+
+const GERMAN: &str = "
+short = cd
+greeting = Hallo, {name}!
+";
+
+fn contents(runner: &mut ApplicationRunner<TestState>) -> Vec<String> {
+    <&Content>::query()
+        .iter(runner.world_mut())
+        .map(|content| content.text.clone())
+        .collect()
+}
+
+fn render_primitives(runner: &mut ApplicationRunner<TestState>) -> Vec::<RenderPrimitive> {
+    <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect()
+}
+
+fn build_runner(root_func: fn() -> RootNode<TestState>, localisation: LocalisationBuilder) -> ApplicationRunner<TestState> {
+    Application::new(TestState::default(), root_func)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(localisation)
+        .with_builder(world_logging())
+        .build()
+        .unwrap()
+}
+
+#[topo::nested]
+fn translated_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <text content=tr!("greeting", name = "Sam") font_size=16 colour=(255, 255, 255, 255) />
+        </root>
+    )
+}
+
+#[test]
+fn text_content_is_translated() {
+    let mut runner = build_runner(translated_text_app_root, localisation("en").with_catalog("en", ENGLISH));
+
+    runner.run_once();
+
+    assert_eq!(contents(&mut runner), vec!("Hello, Sam!".to_string()));
+    assert_eq!(render_primitives(&mut runner).iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 110], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "Hello, Sam!")), 16, Direction::LeftToRight)), true);
+}
+
+#[test]
+fn switching_locale_recomposes_translated_text() {
+    let mut runner = build_runner(translated_text_app_root, localisation("en").with_catalog("en", ENGLISH).with_catalog("de", GERMAN));
+
+    runner.run_once();
+
+    assert_eq!(contents(&mut runner), vec!("Hello, Sam!".to_string()));
+
+    runner.set_locale("de");
+    runner.run_once();
+
+    assert_eq!(contents(&mut runner), vec!("Hallo, Sam!".to_string()));
+    assert_eq!(render_primitives(&mut runner).iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 110], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "Hallo, Sam!")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn translated_text_in_stack_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content=tr!("short") font_size=16 colour=(255, 255, 255, 255) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn switching_locale_relayouts_translated_text() {
+    let mut runner = build_runner(translated_text_in_stack_app_root, localisation("en").with_catalog("en", ENGLISH).with_catalog("de", GERMAN));
+
+    runner.run_once();
+
+    assert_eq!(render_primitives(&mut runner).iter().any(|change| *change == RenderPrimitive::text([0, 0], [20, 110], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "ab")), 16, Direction::LeftToRight)), true);
+
+    runner.set_locale("de");
+    runner.run_once();
+
+    assert_eq!(render_primitives(&mut runner).iter().any(|change| *change == RenderPrimitive::text([0, 0], [19, 110], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "cd")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn fallback_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content=tr!("short") font_size=16 colour=(255, 255, 255, 255) />
+                <text content=tr!("total", amount = 3) font_size=16 colour=(255, 255, 255, 255) />
+                <text content=tr!("missing") font_size=16 colour=(255, 255, 255, 255) />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn missing_messages_fall_back_to_fallback_locale_then_key() {
+    let mut runner = build_runner(fallback_text_app_root, localisation("de-AT").with_fallback_locale("en").with_catalog("en", ENGLISH).with_catalog("de", GERMAN));
+
+    runner.run_once();
+
+    let mut contents = contents(&mut runner);
+    contents.sort();
+
+    assert_eq!(contents, vec!("Total: 3".to_string(), "cd".to_string(), "missing".to_string()));
+}
+
+#[test]
+fn messages_are_loaded_from_catalog_files() {
+    let mut runner = build_runner(translated_text_app_root, localisation("fr").with_catalog_file("fr", concat!(env!("CARGO_MANIFEST_DIR"), "/tests/catalogs/fr.catalog")));
+
+    runner.run_once();
+
+    assert_eq!(contents(&mut runner), vec!("Bonjour, Sam !".to_string()));
+}
+
+#[test]
+fn missing_catalog_file_fails_to_build() {
+    let result = Application::new(TestState::default(), translated_text_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(localisation("fr").with_catalog_file("fr", "missing.catalog"))
+        .build();
+
+    assert_eq!(result.is_err(), true);
+}
+
+#[test]
+fn plural_variants_are_selected_by_count() {
+    let mut localisation = Localisation::new("en");
+    localisation.add_catalog("en", MessageCatalog::parse(ENGLISH).unwrap());
+    localisation.add_catalog("fr", MessageCatalog::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/catalogs/fr.catalog")).unwrap());
+
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(1))]), "1 file");
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(0))]), "0 files");
+
+    localisation.set_locale("fr");
+
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(0))]), "0 fichier");
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(2))]), "2 fichiers");
+}
+
+#[test]
+fn plural_rules_follow_locale_conventions() {
+    let localisation = Localisation::new("ru");
+
+    assert_eq!(localisation.plural_category(1.0), PluralCategory::One);
+    assert_eq!(localisation.plural_category(3.0), PluralCategory::Few);
+    assert_eq!(localisation.plural_category(11.0), PluralCategory::Many);
+    assert_eq!(localisation.plural_category(22.0), PluralCategory::Few);
+    assert_eq!(Localisation::new("ja").plural_category(1.0), PluralCategory::Other);
+}
+
+#[test]
+fn plural_rule_hook_overrides_default() {
+    let mut localisation = Localisation::new("en");
+    localisation.add_catalog("en", MessageCatalog::parse("files[zero] = no files\nfiles[other] = {count} files").unwrap());
+    localisation.set_plural_rule("en", std::sync::Arc::new(|count: f64| if count == 0.0 { PluralCategory::Zero } else { PluralCategory::Other }));
+
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(0))]), "no files");
+    assert_eq!(localisation.translate("files", &[("count", TranslationArg::from(1))]), "1 files");
+}
+
+#[test]
+fn numbers_and_dates_are_formatted_through_locale_hooks() {
+    let mut localisation = Localisation::new("en");
+    localisation.add_catalog("en", MessageCatalog::parse(ENGLISH).unwrap());
+    let date = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+    assert_eq!(localisation.translate("total", &[("amount", TranslationArg::from(2.5))]), "Total: 2.5");
+    assert_eq!(localisation.translate("updated", &[("date", TranslationArg::from(date))]), "Updated 2023-11-14");
+
+    localisation.set_number_formatter("en", std::sync::Arc::new(|number: f64| format!("{:.2}", number)));
+    localisation.set_date_formatter("en", std::sync::Arc::new(|_: SystemTime| "yesterday".to_string()));
+
+    assert_eq!(localisation.translate("total", &[("amount", TranslationArg::from(2.5))]), "Total: 2.50");
+    assert_eq!(localisation.translate("updated", &[("date", TranslationArg::from(date))]), "Updated yesterday");
+}
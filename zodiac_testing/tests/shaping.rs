@@ -0,0 +1,158 @@
+use legion::*;
+use mox::mox;
+use zodiac_testing::*;
+use zodiac::*;
+
+fn glyph_positions(runner: &mut ApplicationRunner<TestState>) -> Vec<Vec<(usize, f32)>> {
+    <&TextLines>::query()
+        .iter(runner.world_mut())
+        .flat_map(|text_lines| text_lines.runs.iter())
+        .map(|run| run.glyphs.iter().map(|glyph| (glyph.cluster, glyph.left)).collect())
+        .collect()
+}
+
+#[topo::nested]
+fn text_shapes_ligatures_into_one_cluster_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="office".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_shapes_ligatures_into_one_cluster() {
+    let mut runner = Application::new(TestState::default(), text_shapes_ligatures_into_one_cluster_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    assert_eq!(glyph_positions(&mut runner), vec!(vec!((0, 0.0), (1, 9.7890625), (4, 25.2578125), (5, 34.0546875))));
+}
+
+#[topo::nested]
+fn text_positions_combining_marks_on_base_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="x\u{0301}".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_positions_combining_marks_on_base() {
+    let mut runner = Application::new(TestState::default(), text_positions_combining_marks_on_base_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    assert_eq!(glyph_positions(&mut runner), vec!(vec!((0, 0.0), (0, 8.765625))));
+}
+
+#[topo::nested]
+fn text_joins_arabic_right_to_left_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="\u{0633}\u{0644}\u{0627}\u{0645}".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_joins_arabic_right_to_left() {
+    let mut runner = Application::new(TestState::default(), text_joins_arabic_right_to_left_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let directions: Vec<Direction> = <&TextLines>::query()
+        .iter(runner.world_mut())
+        .flat_map(|text_lines| text_lines.runs.iter())
+        .map(|run| run.direction)
+        .collect();
+
+    assert_eq!(directions, vec!(Direction::RightToLeft));
+    assert_eq!(glyph_positions(&mut runner), vec!(vec!((6, 0.0), (2, 9.90625), (0, 19.453125))));
+}
+
+#[topo::nested]
+fn text_reorders_mixed_direction_runs_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="abc \u{05D0}\u{05D1}\u{05D2} def".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_reorders_mixed_direction_runs() {
+    let mut runner = Application::new(TestState::default(), text_reorders_mixed_direction_runs_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [91, 110], [255, 255, 255, 25], vec!(
+        RenderTextRun::new([0, 0], "abc "),
+        RenderTextRun::new([34, 0], "\u{05D0}\u{05D1}\u{05D2}"),
+        RenderTextRun::new([60, 0], " def")), 16, Direction::LeftToRight)), true);
+    assert_eq!(glyph_positions(&mut runner)[1], vec!((4, 0.0), (2, 6.59375), (0, 15.84375)));
+}
+
+#[topo::nested]
+fn text_reorders_mixed_direction_runs_in_right_to_left_paragraph_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack direction=Direction::RightToLeft>
+                <text content="abc \u{05D0}\u{05D1}\u{05D2} def".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_reorders_mixed_direction_runs_in_right_to_left_paragraph() {
+    let mut runner = Application::new(TestState::default(), text_reorders_mixed_direction_runs_in_right_to_left_paragraph_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([9, 0], [91, 110], [255, 255, 255, 25], vec!(
+        RenderTextRun::new([9, 0], "def"),
+        RenderTextRun::new([35, 0], " \u{05D0}\u{05D1}\u{05D2} "),
+        RenderTextRun::new([71, 0], "abc")), 16, Direction::RightToLeft)), true);
+}
\ No newline at end of file
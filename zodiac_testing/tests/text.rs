@@ -1,6 +1,7 @@
 use legion::*;
 use mox::mox;
 use zodiac_testing::*;
+use zodiac_layout::*;
 use zodiac::*;
 
 #[topo::nested]
@@ -28,7 +29,7 @@ fn text_gets_output() {
         .collect();  
     
     assert_eq!(changes.len(), 1);
-    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 110], [255, 255, 255, 25], "abc".to_string(), 32)), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 110], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "abc")), 32, Direction::LeftToRight)), true);
 }
 
 #[topo::nested]
@@ -58,5 +59,464 @@ fn text_gets_output_in_stack() {
         .collect();  
     
     assert_eq!(changes.len(), 1);
-    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 110], [255, 255, 255, 25], "abc".to_string(), 32)), true);
-}
\ No newline at end of file
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [58, 110], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "abc")), 32, Direction::LeftToRight)), true);
+}
+#[topo::nested]
+fn text_gets_output_with_inherited_direction_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack direction=Direction::RightToLeft>
+                <text content="abc".to_string() font_size=32 colour=(255, 255, 255, 25) width=40 />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_gets_output_with_inherited_direction() {
+    let mut runner = Application::new(TestState::default(), text_gets_output_with_inherited_direction_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([60, 0], [40, 110], [255, 255, 255, 25], vec!(RenderTextRun::new([60, 0], "abc")), 32, Direction::RightToLeft)), true);
+}
+
+#[test]
+fn font_registry_measures_text_with_bundled_font() {
+    let fonts = create_font_registry().unwrap();
+
+    let style = |font_size| TextStyle { font_size: Some(font_size), ..TextStyle::default() };
+
+    assert_eq!(measure_text(&fonts, &[TextSpan::new("abc", style(32.0))], &TextFormat::default(), f32::INFINITY, Direction::LeftToRight), TextSize { width: 57.515625, height: 37.25 });
+    assert_eq!(measure_text(&fonts, &[TextSpan::new("", style(16.0))], &TextFormat::default(), f32::INFINITY, Direction::LeftToRight), TextSize { width: 0.0, height: 18.625 });
+}
+
+#[topo::nested]
+fn text_gets_intrinsic_height_in_vertical_stack_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="abc".to_string() font_size=32 colour=(255, 255, 255, 25) />
+                <text content="def".to_string() font_size=16 colour=(255, 255, 255, 25) />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_gets_intrinsic_height_in_vertical_stack() {
+    let mut runner = Application::new(TestState::default(), text_gets_intrinsic_height_in_vertical_stack_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 37], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "abc")), 32, Direction::LeftToRight)), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 37], [100, 19], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 37], "def")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn stack_wraps_around_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <horizontal_stack sizing=Sizing::WrapContent>
+                    <text content="ab".to_string() font_size=16 colour=(255, 255, 255, 25) />
+                    <text content="hi".to_string() font_size=16 colour=(255, 255, 255, 25) />
+                </horizontal_stack>
+                <rect
+                    colour=(255, 255, 255, 25)
+                    stroke_colour=(50, 75, 255, 255)
+                    stroke_width=2
+                    corner_radii=(50, 0, 50, 50)
+                />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn stack_wraps_around_text() {
+    let mut runner = Application::new(TestState::default(), stack_wraps_around_text_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [20, 19], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "ab")), 16, Direction::LeftToRight)), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([20, 0], [15, 19], [255, 255, 255, 25], vec!(RenderTextRun::new([20, 0], "hi")), 16, Direction::LeftToRight)), true);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::rectangle([0, 19], [100, 91], [255, 255, 255, 25], [50, 75, 255, 255], 2, [50, 0, 50, 50])), true);
+}
+
+#[topo::nested]
+fn text_wraps_words_into_lines_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox jumps".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_wraps_words_into_lines() {
+    let mut runner = Application::new(TestState::default(), text_wraps_words_into_lines_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 56], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "the quick"), RenderTextRun::new([0, 19], "brown fox"), RenderTextRun::new([0, 37], "jumps")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_aligns_wrapped_lines_to_centre_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox jumps".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word text_align=TextAlign::Center />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_aligns_wrapped_lines_to_centre() {
+    let mut runner = Application::new(TestState::default(), text_aligns_wrapped_lines_to_centre_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 56], [255, 255, 255, 25], vec!(RenderTextRun::new([13, 0], "the quick"), RenderTextRun::new([11, 19], "brown fox"), RenderTextRun::new([26, 37], "jumps")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_aligns_wrapped_lines_to_end_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox jumps".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word text_align=TextAlign::End />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_aligns_wrapped_lines_to_end() {
+    let mut runner = Application::new(TestState::default(), text_aligns_wrapped_lines_to_end_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 56], [255, 255, 255, 25], vec!(RenderTextRun::new([26, 0], "the quick"), RenderTextRun::new([21, 19], "brown fox"), RenderTextRun::new([51, 37], "jumps")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_justifies_all_but_last_line_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox jumps".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word text_align=TextAlign::Justify />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_justifies_all_but_last_line() {
+    let mut runner = Application::new(TestState::default(), text_justifies_all_but_last_line_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 56], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "the"), RenderTextRun::new([57, 0], "quick"), RenderTextRun::new([0, 19], "brown"), RenderTextRun::new([76, 19], "fox"), RenderTextRun::new([0, 37], "jumps")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_truncates_wrapped_lines_with_ellipsis_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox jumps".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word max_lines=2 />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_truncates_wrapped_lines_with_ellipsis() {
+    let mut runner = Application::new(TestState::default(), text_truncates_wrapped_lines_with_ellipsis_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 37], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "the quick"), RenderTextRun::new([0, 19], "brown fox\u{2026}")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_truncates_unwrapped_line_with_ellipsis_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="the quick brown fox".to_string() font_size=16 colour=(255, 255, 255, 25) width=100 max_lines=1 />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_truncates_unwrapped_line_with_ellipsis() {
+    let mut runner = Application::new(TestState::default(), text_truncates_unwrapped_line_with_ellipsis_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 19], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "the quick\u{2026}")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_wraps_characters_with_line_height_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <text content="abcdefghij".to_string() font_size=16 colour=(255, 255, 255, 25) width=40 text_wrap=TextWrap::Character line_height=24 />
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_wraps_characters_with_line_height() {
+    let mut runner = Application::new(TestState::default(), text_wraps_characters_with_line_height_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [40, 72], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "abcd"), RenderTextRun::new([0, 24], "efgh"), RenderTextRun::new([0, 48], "ij")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_uses_registered_font_weight_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="abc".to_string() font_size=16 font_weight=700 colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_uses_registered_font_weight() {
+    let mut runner = Application::new(TestState::default(), text_uses_registered_font_weight_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(fonts().with_font_file(DEFAULT_FONT_FAMILY, FontWeight::BOLD, FontStyle::Normal, concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fonts/DejaVuSans-Bold.ttf")))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [32, 110], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "abc").with_font(FontId { id: 1 })), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn text_falls_back_for_missing_glyphs_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <text content="a\u{01C4}b".to_string() font_size=16 font_family="Mono".to_string() colour=(255, 255, 255, 25) />
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn text_falls_back_for_missing_glyphs() {
+    let mut runner = Application::new(TestState::default(), text_falls_back_for_missing_glyphs_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(fonts().with_font_file("Mono", FontWeight::NORMAL, FontStyle::Normal, concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fonts/DejaVuSansMono.ttf")))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [42, 110], [255, 255, 255, 25], vec!(RenderTextRun::new([0, 0], "a").with_font(FontId { id: 1 }), RenderTextRun::new([10, 0], "\u{01C4}"), RenderTextRun::new([32, 0], "b").with_font(FontId { id: 1 })), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn rich_text_lays_out_spans_as_one_paragraph_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <horizontal_stack>
+                <rich_text font_size=16 colour=(255, 255, 255, 25)>
+                    <span content="Total: ".to_string() />
+                    <span content="42".to_string() font_weight=700 colour=(255, 0, 0, 255) />
+                    <span content=" items".to_string() />
+                </rich_text>
+            </horizontal_stack>
+        </root>
+    )
+}
+
+#[test]
+fn rich_text_lays_out_spans_as_one_paragraph() {
+    let mut runner = Application::new(TestState::default(), rich_text_lays_out_spans_as_one_paragraph_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(150, 110)))
+        .with_builder(fonts().with_font_file(DEFAULT_FONT_FAMILY, FontWeight::BOLD, FontStyle::Normal, concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fonts/DejaVuSans-Bold.ttf")))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [120, 110], [255, 255, 255, 25], vec!(
+        RenderTextRun::new([0, 0], "Total: "),
+        RenderTextRun::new([48, 0], "42").with_font(FontId { id: 1 }).with_colour([255, 0, 0, 255]),
+        RenderTextRun::new([70, 0], " items")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn rich_text_wraps_styled_spans_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <vertical_stack>
+                <rich_text font_size=16 colour=(255, 255, 255, 25) width=100 text_wrap=TextWrap::Word>
+                    <span content="the quick ".to_string() />
+                    <span content="brown".to_string() underline=true background=(0, 0, 255, 255) />
+                    <span content=" fox".to_string() font_size=32 />
+                </rich_text>
+            </vertical_stack>
+        </root>
+    )
+}
+
+#[test]
+fn rich_text_wraps_styled_spans() {
+    let mut runner = Application::new(TestState::default(), rich_text_wraps_styled_spans_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 120)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+
+    let changes: Vec::<RenderPrimitive> = <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect();  
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 112], [255, 255, 255, 25], vec!(
+        RenderTextRun::new([0, 0], "the quick"),
+        RenderTextRun::new([0, 37], "brown").with_underline().with_background([0, 0, 255, 255]),
+        RenderTextRun::new([0, 75], "fox").with_font_size(32)), 16, Direction::LeftToRight)), true);
+}
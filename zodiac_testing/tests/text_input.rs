@@ -0,0 +1,437 @@
+use legion::*;
+use mox::mox;
+use moxie::Key;
+use zodiac_testing::*;
+use zodiac::*;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct InputState {
+    text: String,
+    submitted: String
+}
+
+impl State for InputState {
+}
+
+fn input_root() -> RootBuilder<InputState> {
+    RootBuilder::<InputState>::new()
+}
+
+fn text_edit_state(runner: &mut ApplicationRunner<impl State>) -> TextEditState {
+    <&TextEditState>::query()
+        .iter(runner.world_mut())
+        .next()
+        .unwrap()
+        .clone()
+}
+
+fn render_primitives(runner: &mut ApplicationRunner<impl State>) -> Vec::<RenderPrimitive> {
+    <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect()
+}
+
+fn focus(runner: &mut ApplicationRunner<impl State>) {
+    runner.send_event(SystemEvent::Input(SystemInputEventType::PointerPressed(5.0, 5.0, KeyModifiers::default())));
+}
+
+fn type_text(runner: &mut ApplicationRunner<impl State>, text: &str) {
+    runner.send_event(SystemEvent::Input(SystemInputEventType::TextReceived(text.to_string())));
+}
+
+fn press(runner: &mut ApplicationRunner<impl State>, key: KeyCode, modifiers: KeyModifiers) {
+    runner.send_event(SystemEvent::Input(SystemInputEventType::KeyPressed(key, modifiers)));
+}
+
+#[topo::nested]
+fn text_input_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <text_input width=100 height=20 font_size=16 colour=(255, 255, 255, 255) placeholder="name".to_string() />
+        </root>
+    )
+}
+
+fn build_text_input_runner() -> ApplicationRunner<TestState> {
+    Application::new(TestState::default(), text_input_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn text_input_renders_dimmed_placeholder_when_empty() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text_input([0, 0], [100, 20], [255, 255, 255, 127], vec!(RenderTextRun::new([0, 0], "name")), 16, Direction::LeftToRight, RenderTextEditing::default())), true);
+}
+
+#[test]
+fn text_input_renders_typed_text_with_caret_when_focused() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "ab");
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+    
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text_input([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "ab")), 16, Direction::LeftToRight, RenderTextEditing::default().with_caret([20, 0], [1, 19]))), true);
+}
+
+#[test]
+fn text_input_ignores_typing_until_focused() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    type_text(&mut runner, "ab");
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "");
+}
+
+#[test]
+fn text_input_blurs_when_pointer_pressed_outside() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    runner.run_once();
+    runner.send_event(SystemEvent::Input(SystemInputEventType::PointerPressed(50.0, 50.0, KeyModifiers::default())));
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).focused, false);
+}
+
+#[test]
+fn text_input_renders_selection() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "ab");
+    press(&mut runner, KeyCode::Left, KeyModifiers::shift());
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+    
+    assert_eq!(text_edit_state(&mut runner).selected_text(), "b");
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text_input([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "ab")), 16, Direction::LeftToRight, RenderTextEditing::default().with_selection([10, 0], [10, 19]).with_caret([10, 0], [1, 19]))), true);
+}
+
+#[test]
+fn text_input_moves_and_deletes_by_word() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "one two three");
+    press(&mut runner, KeyCode::Left, KeyModifiers::control());
+    press(&mut runner, KeyCode::Backspace, KeyModifiers::control());
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "one three");
+    assert_eq!(state.caret, 4);
+}
+
+#[test]
+fn text_input_moves_to_home_and_end() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "abc");
+    press(&mut runner, KeyCode::Home, KeyModifiers::default());
+    type_text(&mut runner, "x");
+    press(&mut runner, KeyCode::End, KeyModifiers::default());
+    type_text(&mut runner, "y");
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "xabcy");
+}
+
+#[test]
+fn text_input_replaces_selection_when_typing() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "hello");
+    press(&mut runner, KeyCode::Letter('a'), KeyModifiers::control());
+    type_text(&mut runner, "bye");
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "bye");
+    assert_eq!(state.has_selection(), false);
+}
+
+#[test]
+fn text_input_undoes_and_redoes_edits() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "hello world");
+    press(&mut runner, KeyCode::Backspace, KeyModifiers::control());
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "hello ");
+
+    press(&mut runner, KeyCode::Letter('z'), KeyModifiers::control());
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "hello world");
+
+    press(&mut runner, KeyCode::Letter('y'), KeyModifiers::control());
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "hello ");
+}
+
+#[test]
+fn text_input_inserts_received_text() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    runner.send_event(SystemEvent::Input(SystemInputEventType::TextReceived("ab\u{8}c".to_string())));
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "abc");
+}
+
+#[test]
+fn text_input_copies_and_pastes_through_clipboard() {
+    let clipboard = create_application_clipboard();
+    let mut runner = Application::new(TestState::default(), text_input_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(ClipboardBuilder::new(clipboard.clone()))
+        .build()
+        .unwrap();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "abc");
+    press(&mut runner, KeyCode::Left, KeyModifiers::shift());
+    press(&mut runner, KeyCode::Letter('c'), KeyModifiers::control());
+    press(&mut runner, KeyCode::End, KeyModifiers::default());
+    press(&mut runner, KeyCode::Letter('v'), KeyModifiers::control());
+    runner.run_once();
+
+    assert_eq!(clipboard.get_text(), Some("c".to_string()));
+    assert_eq!(text_edit_state(&mut runner).value, "abcc");
+}
+
+#[test]
+fn text_input_renders_underlined_composition() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "a");
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("bc".to_string(), 2)));
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+    
+    assert_eq!(text_edit_state(&mut runner).value, "a");
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text_input([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "a"), RenderTextRun::new([10, 0], "bc").with_underline()), 16, Direction::LeftToRight, RenderTextEditing::default().with_caret([29, 0], [1, 19]))), true);
+}
+
+#[test]
+fn text_input_replaces_selection_with_composition() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "abc");
+    press(&mut runner, KeyCode::Left, KeyModifiers::shift());
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("x".to_string(), 1)));
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "ab");
+    assert_eq!(state.display_text(None), "abx");
+}
+
+#[test]
+fn text_input_ignores_keys_while_composing() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("ni".to_string(), 2)));
+    press(&mut runner, KeyCode::Backspace, KeyModifiers::default());
+    type_text(&mut runner, "x");
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "");
+    assert_eq!(state.display_text(None), "ni");
+}
+
+#[test]
+fn text_input_commits_composition() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("にほん".to_string(), 9)));
+    runner.run_once();
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionCommitted("日本".to_string())));
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "日本");
+    assert_eq!(state.caret, 6);
+    assert_eq!(state.is_composing(), false);
+}
+
+#[test]
+fn text_input_cancels_composition() {
+    let mut runner = build_text_input_runner();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "a");
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("b".to_string(), 1)));
+    runner.run_once();
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionCancelled));
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.display_text(None), "a");
+    assert_eq!(state.is_composing(), false);
+}
+
+#[topo::nested]
+#[illicit::from_env(state: &Key<InputState>)]
+fn controlled_text_input_app_root() -> RootNode<InputState> {
+    mox!(
+        <input_root>
+            <text_input
+                width=100
+                height=20
+                font_size=16
+                colour=(255, 255, 255, 255)
+                value=state.text.clone()
+                on_change=TextHandler::new(|state: &mut InputState, text: &str| state.text = text.to_string())
+                on_submit=TextHandler::new(|state: &mut InputState, text: &str| state.submitted = text.to_string()) />
+        </input_root>
+    )
+}
+
+fn build_controlled_text_input_runner(state: InputState) -> ApplicationRunner<InputState> {
+    Application::new(state, controlled_text_input_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn text_input_shows_state_value() {
+    let mut runner = build_controlled_text_input_runner(InputState { text: "abc".to_string(), ..InputState::default() });
+
+    runner.run_once();
+
+    assert_eq!(text_edit_state(&mut runner).value, "abc");
+}
+
+#[test]
+fn text_input_updates_state_on_change() {
+    let mut runner = build_controlled_text_input_runner(InputState::default());
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "hi");
+    runner.run_once();
+    runner.run_once();
+    runner.run_once();
+
+    let state = runner.run_once();
+
+    assert_eq!(state.text, "hi");
+    assert_eq!(text_edit_state(&mut runner).value, "hi");
+    assert_eq!(text_edit_state(&mut runner).caret, 2);
+}
+
+#[test]
+fn text_input_updates_state_on_submit() {
+    let mut runner = build_controlled_text_input_runner(InputState::default());
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "go");
+    press(&mut runner, KeyCode::Enter, KeyModifiers::default());
+    runner.run_once();
+    runner.run_once();
+    runner.run_once();
+
+    let state = runner.run_once();
+
+    assert_eq!(state.submitted, "go");
+    assert_eq!(text_edit_state(&mut runner).value, "go");
+}
+
+#[test]
+fn text_input_updates_state_when_composition_committed() {
+    let mut runner = build_controlled_text_input_runner(InputState::default());
+
+    runner.run_once();
+    focus(&mut runner);
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionUpdated("ni".to_string(), 2)));
+    runner.send_event(SystemEvent::Input(SystemInputEventType::CompositionCommitted("日".to_string())));
+    runner.run_once();
+    runner.run_once();
+    runner.run_once();
+
+    let state = runner.run_once();
+
+    assert_eq!(state.text, "日");
+}
+
+#[topo::nested]
+fn multi_line_text_input_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <text_input width=100 height=20 font_size=16 colour=(255, 255, 255, 255) multi_line=true />
+        </root>
+    )
+}
+
+#[test]
+fn multi_line_text_input_inserts_new_lines_and_scrolls_to_caret() {
+    let mut runner = Application::new(TestState::default(), multi_line_text_input_app_root)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(world_logging())
+        .build()
+        .unwrap();
+
+    runner.run_once();
+    focus(&mut runner);
+    type_text(&mut runner, "ab");
+    press(&mut runner, KeyCode::Enter, KeyModifiers::default());
+    type_text(&mut runner, "cd");
+    runner.run_once();
+
+    let state = text_edit_state(&mut runner);
+    assert_eq!(state.value, "ab\ncd");
+    assert_eq!(state.caret, 5);
+    assert_eq!(state.scroll_top, 17.25);
+
+    let changes = render_primitives(&mut runner);
+
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text_input([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "ab"), RenderTextRun::new([0, 1], "cd")), 16, Direction::LeftToRight, RenderTextEditing::default().with_caret([19, 1], [1, 19]))), true);
+}
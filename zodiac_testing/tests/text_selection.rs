@@ -0,0 +1,170 @@
+use legion::*;
+use mox::mox;
+use zodiac_testing::*;
+use zodiac::*;
+
+fn render_primitives(runner: &mut ApplicationRunner<TestState>) -> Vec::<RenderPrimitive> {
+    <&RenderPrimitive>::query()
+        .iter(runner.world_mut())
+        .map(|change| change.clone())
+        .collect()
+}
+
+fn send_input(runner: &mut ApplicationRunner<TestState>, input: SystemInputEventType) {
+    runner.send_event(SystemEvent::Input(input));
+}
+
+fn drag(runner: &mut ApplicationRunner<TestState>, from: (f32, f32), to: (f32, f32)) {
+    send_input(runner, SystemInputEventType::PointerPressed(from.0, from.1, KeyModifiers::default()));
+    send_input(runner, SystemInputEventType::PointerMoved(to.0, to.1, KeyModifiers::default()));
+    send_input(runner, SystemInputEventType::PointerReleased(to.0, to.1, KeyModifiers::default()));
+}
+
+fn copy(runner: &mut ApplicationRunner<TestState>) {
+    send_input(runner, SystemInputEventType::KeyPressed(KeyCode::Letter('c'), KeyModifiers::control()));
+}
+
+fn build_runner(root_func: fn() -> RootNode<TestState>, clipboard: &ApplicationClipboard) -> ApplicationRunner<TestState> {
+    Application::new(TestState::default(), root_func)
+        .with_builders(&mut test_builders(Dimensions::new(100, 110)))
+        .with_builder(ClipboardBuilder::new(clipboard.clone()))
+        .with_builder(world_logging())
+        .build()
+        .unwrap()
+}
+
+#[topo::nested]
+fn selectable_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <text content="abcd".to_string() width=100 height=20 font_size=16 colour=(255, 255, 255, 255) selectable=true />
+        </root>
+    )
+}
+
+#[test]
+fn selectable_text_renders_dragged_selection_behind_text() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(selectable_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (1.0, 5.0), (25.0, 5.0));
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "abcd")), 16, Direction::LeftToRight)
+        .with_selection([0, 0], [29, 19])), true);
+}
+
+#[test]
+fn selectable_text_copies_selection_to_clipboard() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(selectable_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (12.0, 5.0), (40.0, 5.0));
+    runner.run_once();
+    copy(&mut runner);
+    runner.run_once();
+
+    assert_eq!(clipboard.get_text(), Some("bcd".to_string()));
+}
+
+#[test]
+fn selectable_text_extends_selection_with_shift() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(selectable_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (1.0, 5.0), (12.0, 5.0));
+    runner.run_once();
+    send_input(&mut runner, SystemInputEventType::PointerPressed(25.0, 5.0, KeyModifiers::shift()));
+    send_input(&mut runner, SystemInputEventType::PointerReleased(25.0, 5.0, KeyModifiers::shift()));
+    copy(&mut runner);
+    runner.run_once();
+
+    assert_eq!(clipboard.get_text(), Some("abc".to_string()));
+}
+
+#[test]
+fn selectable_text_clears_selection_when_pointer_pressed_outside() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(selectable_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (1.0, 5.0), (25.0, 5.0));
+    runner.run_once();
+    send_input(&mut runner, SystemInputEventType::PointerPressed(50.0, 50.0, KeyModifiers::default()));
+    copy(&mut runner);
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+
+    assert_eq!(clipboard.get_text(), None);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "abcd")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn unselectable_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <text content="abcd".to_string() width=100 height=20 font_size=16 colour=(255, 255, 255, 255) />
+        </root>
+    )
+}
+
+#[test]
+fn text_is_not_selectable_by_default() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(unselectable_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (1.0, 5.0), (25.0, 5.0));
+    runner.run_once();
+    copy(&mut runner);
+    runner.run_once();
+
+    let changes = render_primitives(&mut runner);
+
+    assert_eq!(clipboard.get_text(), None);
+    assert_eq!(changes.iter().any(|change| *change == RenderPrimitive::text([0, 0], [100, 20], [255, 255, 255, 255], vec!(RenderTextRun::new([0, 0], "abcd")), 16, Direction::LeftToRight)), true);
+}
+
+#[topo::nested]
+fn selectable_rich_text_app_root() -> RootNode<TestState> {
+    mox!(
+        <root>
+            <rich_text width=100 height=20 font_size=16 colour=(255, 255, 255, 255) selectable=true>
+                <span content="ab".to_string() />
+                <span content="cd".to_string() underline=true />
+            </rich_text>
+        </root>
+    )
+}
+
+#[test]
+fn selectable_rich_text_copies_across_spans() {
+    let clipboard = create_application_clipboard();
+    let mut runner = build_runner(selectable_rich_text_app_root, &clipboard);
+
+    runner.run_once();
+    drag(&mut runner, (12.0, 5.0), (25.0, 5.0));
+    runner.run_once();
+    copy(&mut runner);
+    runner.run_once();
+
+    assert_eq!(clipboard.get_text(), Some("bc".to_string()));
+}
+
+#[test]
+fn in_memory_clipboard_round_trips_text() {
+    let mut clipboard = InMemoryClipboard::default();
+
+    assert_eq!(clipboard.get_text(), None);
+
+    clipboard.set_text("copied");
+
+    assert_eq!(clipboard.get_text(), Some("copied".to_string()));
+}